@@ -23,10 +23,14 @@ use std::{
 	net::SocketAddr,
 	str::FromStr,
 	sync::{Arc, Mutex},
+	time::Duration,
 };
-use tracing::info;
+use tracing::{info, warn};
 use warp::{Filter, Reply};
 
+/// Time given to in-flight requests to complete after the shutdown is triggered
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
 pub struct Server<T: Database> {
 	pub db: T,
 	pub cfg: RuntimeConfig,
@@ -34,7 +38,8 @@ pub struct Server<T: Database> {
 	pub state: Arc<Mutex<State>>,
 	pub version: String,
 	pub network_version: String,
-	pub node_client: rpc::Client,
+	/// Node client, not set in read-only mode, which disables transaction submission
+	pub node_client: Option<rpc::Client>,
 	pub ws_clients: v2::types::WsClients,
 	pub shutdown: Controller<String>,
 }
@@ -80,9 +85,32 @@ impl<T: Database + Clone + Send + Sync + 'static> Server<T> {
 			.unwrap();
 		info!("RPC running on http://{host}:{port}");
 		// warp graceful shutdown expects a signal that is [`Future<Output = ()>`]
-		let shutdown_signal = self.shutdown.triggered_shutdown().map(|_| ());
+		let ws_clients = self.ws_clients.clone();
+		let shutdown_signal = self.shutdown.triggered_shutdown().then(|_| async move {
+			info!("Shutdown triggered, closing web socket connections");
+			ws_clients.close().await;
+		});
 		let (_, server) = warp::serve(routes).bind_with_graceful_shutdown(addr, shutdown_signal);
 
-		server
+		// in-flight requests are given a bounded time to complete
+		let grace_period_expired = self
+			.shutdown
+			.triggered_shutdown()
+			.then(|_| tokio::time::sleep(SHUTDOWN_GRACE_PERIOD));
+		let server = async move {
+			tokio::select! {
+				_ = server => info!("HTTP server stopped"),
+				_ = grace_period_expired => warn!("HTTP server shutdown grace period expired"),
+			}
+		};
+
+		// shutdown will wait for the server to stop, unless it has already completed
+		let server = self.shutdown.with_delay(server);
+		async move {
+			let Ok(server) = server else {
+				return;
+			};
+			server.await
+		}
 	}
 }
@@ -3,14 +3,13 @@ use crate::{
 	api::v1::types::{Extrinsics, ExtrinsicsDataResponse},
 	data::{Database, Key},
 	types::{Mode, OptionBlockRange, State},
-	utils::calculate_confidence,
+	utils::{calculate_confidence, decode_extrinsic},
 };
 use avail_subxt::{
 	api::runtime_types::{da_control::pallet::Call, da_runtime::RuntimeCall},
 	primitives::AppUncheckedExtrinsic,
 };
 use base64::{engine::general_purpose, Engine};
-use codec::Decode;
 use color_eyre::{eyre::WrapErr, Result};
 use num::{BigUint, FromPrimitive};
 use std::sync::{Arc, Mutex};
@@ -112,7 +111,7 @@ pub fn appdata(
 				e.iter()
 					.enumerate()
 					.map(|(i, raw)| {
-						<_ as Decode>::decode(&mut &raw[..])
+						decode_extrinsic(raw)
 							.wrap_err(format!("Couldn't decode AvailExtrinsic num {i}"))
 					})
 					.collect::<Result<Vec<_>>>()
@@ -191,7 +191,7 @@ pub fn routes(
 	state: Arc<Mutex<State>>,
 	config: RuntimeConfig,
 	identity_config: IdentityConfig,
-	rpc_client: Client,
+	rpc_client: Option<Client>,
 	ws_clients: WsClients,
 	db: impl Database + Clone + Send,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
@@ -203,7 +203,7 @@ pub fn routes(
 	let app_id = config.app_id.as_ref();
 	let pair_signer = <PairSigner<AvailConfig, Pair>>::new(identity_config.avail_key_pair);
 
-	let submitter = app_id.map(|&app_id| {
+	let submitter = app_id.zip(rpc_client).map(|(&app_id, rpc_client)| {
 		Arc::new(transactions::Submitter {
 			rpc_client,
 			app_id,
@@ -235,7 +235,11 @@ mod tests {
 			WsClients, WsError, WsResponse,
 		},
 		data::Key,
-		data::{mem_db, Database},
+		data::{
+			mem_db,
+			rocks_db::{tests::temp_db_path, RocksDB},
+			Database,
+		},
 		types::{BlockRange, OptionBlockRange, RuntimeConfig, State},
 	};
 	use async_trait::async_trait;
@@ -587,6 +591,55 @@ mod tests {
 		);
 	}
 
+	#[tokio::test]
+	async fn block_routes_read_only_stored_block() {
+		let path = temp_db_path("v2-read-only");
+		let db = RocksDB::open(&path).unwrap();
+		db.put(Key::BlockHeader(1), header()).unwrap();
+		db.put(Key::VerifiedCellCount(1), 4u32).unwrap();
+		db.put(Key::AppData(1, 1), Vec::<Vec<u8>>::new()).unwrap();
+		drop(db);
+
+		let config = RuntimeConfig {
+			app_id: Some(1),
+			..Default::default()
+		};
+		let db = RocksDB::open_read_only(&path).unwrap();
+		let state = Arc::new(Mutex::new(db.stored_state(config.app_id).unwrap()));
+
+		let route = super::block_route(config.clone(), state.clone(), db.clone());
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/1")
+			.reply(&route)
+			.await;
+		assert_eq!(
+			response.body(),
+			r#"{"status":"finished","confidence":93.75}"#
+		);
+
+		let route = super::block_header_route(config.clone(), state.clone(), db.clone());
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/1/header")
+			.reply(&route)
+			.await;
+		assert_eq!(response.status(), StatusCode::OK);
+
+		let route = super::block_data_route(config, state, db);
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/blocks/1/data")
+			.reply(&route)
+			.await;
+		assert_eq!(
+			response.body(),
+			r#"{"block_number":1,"data_transactions":[]}"#
+		);
+
+		_ = std::fs::remove_dir_all(path);
+	}
+
 	fn all_topics() -> HashSet<Topic> {
 		vec![
 			Topic::HeaderVerified,
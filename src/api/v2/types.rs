@@ -628,6 +628,26 @@ impl WsClients {
 		clients.insert(subscription_id.to_string(), WsClient::new(subscription));
 	}
 
+	/// Removes subscription, unless another connection has taken over its sender.
+	pub async fn unsubscribe(&self, subscription_id: &str, sender: &Sender) {
+		let mut clients = self.0.write().await;
+		let is_connected_sender = clients
+			.get(subscription_id)
+			.and_then(|client| client.sender.as_ref())
+			.is_some_and(|client_sender| client_sender.same_channel(sender));
+		if is_connected_sender {
+			clients.remove(subscription_id);
+		}
+	}
+
+	/// Sends close message to all connected clients.
+	pub async fn close(&self) {
+		let clients = self.0.read().await;
+		for sender in clients.values().flat_map(|client| client.sender.as_ref()) {
+			let _ = sender.send(Ok(ws::Message::close()));
+		}
+	}
+
 	pub async fn publish(&self, topic: &Topic, message: PublishMessage) -> Result<Vec<Result<()>>> {
 		let clients = self.0.read().await;
 		Ok(clients
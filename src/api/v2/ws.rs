@@ -7,12 +7,15 @@ use crate::{
 	types::{RuntimeConfig, State},
 };
 use color_eyre::{eyre::WrapErr, Result};
-use futures::{FutureExt, StreamExt};
+use futures::{future, FutureExt, StreamExt};
 use serde::Serialize;
-use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
+use std::{
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+use tokio::{sync::mpsc, time};
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use tracing::{error, log::warn};
+use tracing::{error, info, log::warn};
 use warp::ws::{self, Message, WebSocket};
 
 #[allow(clippy::too_many_arguments)]
@@ -50,7 +53,48 @@ pub async fn connect(
 			.wrap_err("Failed to send message")
 	}
 
-	while let Some(result) = web_socket_receiver.next().await {
+	let ping_sender = sender.clone();
+	let ping_interval = Duration::from_secs(config.ws_ping_interval);
+	let ping_task = tokio::task::spawn(async move {
+		let mut interval = time::interval(ping_interval);
+		// First tick completes immediately
+		interval.tick().await;
+		loop {
+			interval.tick().await;
+			if ping_sender.send(Ok(Message::ping(vec![]))).is_err() {
+				break;
+			}
+		}
+	});
+
+	let idle_timeout = Duration::from_secs(config.ws_idle_timeout);
+	let max_lifetime = config.ws_max_lifetime.map(Duration::from_secs);
+	let lifetime_expired = async move {
+		match max_lifetime {
+			Some(max_lifetime) => time::sleep(max_lifetime).await,
+			None => future::pending().await,
+		}
+	};
+	tokio::pin!(lifetime_expired);
+
+	loop {
+		let next_message = tokio::select! {
+			_ = &mut lifetime_expired => {
+				info!("Closing web socket connection after maximum lifetime: {subscription_id}");
+				break;
+			},
+			next_message = time::timeout(idle_timeout, web_socket_receiver.next()) => next_message,
+		};
+
+		let result = match next_message {
+			Ok(Some(result)) => result,
+			Ok(None) => break,
+			Err(_) => {
+				info!("Closing idle web socket connection: {subscription_id}");
+				break;
+			},
+		};
+
 		let message = match result {
 			Err(error) => {
 				error!("Error receiving client message: {error}");
@@ -77,6 +121,9 @@ pub async fn connect(
 			warn!("Error sending message: {error:#}");
 		}
 	}
+
+	ping_task.abort();
+	clients.unsubscribe(&subscription_id, &sender).await;
 }
 
 async fn handle_request(
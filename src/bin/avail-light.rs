@@ -4,7 +4,7 @@ use avail_core::AppId;
 use avail_light::{
 	api,
 	consts::EXPECTED_SYSTEM_VERSION,
-	data::rocks_db::RocksDB,
+	data::{rocks_db::RocksDB, Database, FinalitySyncCheckpoint, Key},
 	maintenance::StaticConfigParams,
 	network::{self, p2p, rpc},
 	shutdown::Controller,
@@ -21,9 +21,7 @@ use color_eyre::{
 use kate_recovery::com::AppData;
 use libp2p::{multiaddr::Protocol, Multiaddr};
 use std::{
-	fs,
 	net::Ipv4Addr,
-	path::Path,
 	sync::{Arc, Mutex},
 };
 use tokio::sync::{broadcast, mpsc, RwLock};
@@ -106,9 +104,8 @@ async fn run(shutdown: Controller<String>) -> Result<()> {
 		warn!("Using default log level: {}", error);
 	}
 
-	if opts.clean && Path::new(&cfg.avail_path).exists() {
-		info!("Cleaning up local state directory");
-		fs::remove_dir_all(&cfg.avail_path).wrap_err("Failed to remove local state directory")?;
+	if opts.read_only {
+		return run_read_only(cfg, identity_cfg, shutdown).await;
 	}
 
 	if cfg.bootstraps.is_empty() {
@@ -118,7 +115,40 @@ async fn run(shutdown: Controller<String>) -> Result<()> {
 	let db =
 		RocksDB::open(&cfg.avail_path).wrap_err("Avail Light could not initialize database")?;
 
-	let cfg_libp2p: LibP2PConfig = (&cfg).into();
+	if opts.clean {
+		info!("Purging chain data from the database");
+		db.purge_chain_data()
+			.wrap_err("Failed to purge chain data")?;
+	}
+
+	if opts.compact {
+		info!("Compacting database");
+		db.compact().wrap_err("Failed to compact database")?;
+	}
+
+	if opts.db_stats {
+		for stats in db
+			.statistics()
+			.wrap_err("Failed to get database statistics")?
+		{
+			info!(
+				"Column family {}: ~{} keys, {} bytes",
+				stats.name, stats.estimated_keys, stats.size_bytes
+			);
+		}
+	}
+
+	// Checkpoint is stored only after its finalized block is checked against the node
+	let imported_checkpoint = opts
+		.import_finality_checkpoint
+		.as_deref()
+		.map(FinalitySyncCheckpoint::import)
+		.transpose()?;
+
+	let mut cfg_libp2p: LibP2PConfig = (&cfg).into();
+	if cfg_libp2p.secret_key.is_none() {
+		cfg_libp2p.secret_key = Some(p2p::stored_secret_key(&db)?);
+	}
 	let (id_keys, peer_id) = p2p::keypair(&cfg_libp2p)?;
 
 	let metric_attributes = MetricAttributes {
@@ -218,6 +248,49 @@ async fn run(shutdown: Controller<String>) -> Result<()> {
 	)
 	.await?;
 
+	if let Some((checkpoint, finalized_hash)) = imported_checkpoint {
+		let block_hash = rpc_client.get_block_hash(checkpoint.number).await?;
+		if block_hash != finalized_hash {
+			Err(eyre!(
+				"Finality sync checkpoint block {} hash doesn't match the node",
+				checkpoint.number
+			))?
+		}
+		let set_id = rpc_client.fetch_set_id_at(block_hash).await?;
+		if set_id != checkpoint.set_id {
+			Err(eyre!(
+				"Finality sync checkpoint set ID {} doesn't match the node set ID {set_id}",
+				checkpoint.set_id
+			))?
+		}
+		// checksum can be recomputed by anyone, so the trusted validator set is checked as well
+		let validator_set = rpc_client.get_validator_set_by_hash(block_hash).await?;
+		if validator_set != checkpoint.validator_set {
+			Err(eyre!(
+				"Finality sync checkpoint validator set doesn't match the node validator set at block {}",
+				checkpoint.number
+			))?
+		}
+		warn!(
+			"Importing finality sync checkpoint at block {}",
+			checkpoint.number
+		);
+		db.put(Key::FinalitySyncCheckpoint, checkpoint)
+			.wrap_err("Failed to store imported finality sync checkpoint")?;
+	}
+
+	if let Some(path) = &opts.export_finality_checkpoint {
+		let checkpoint: FinalitySyncCheckpoint = db
+			.get(Key::FinalitySyncCheckpoint)?
+			.ok_or_else(|| eyre!("Finality sync checkpoint is not stored"))?;
+		let finalized_hash = rpc_client.get_block_hash(checkpoint.number).await?;
+		info!(
+			"Exporting finality sync checkpoint at block {} to {path}",
+			checkpoint.number
+		);
+		checkpoint.export(finalized_hash, path)?;
+	}
+
 	// Subscribing to RPC events before first event is published
 	let publish_rpc_event_receiver = rpc_events.subscribe();
 	let first_header_rpc_event_receiver = rpc_events.subscribe();
@@ -280,11 +353,11 @@ async fn run(shutdown: Controller<String>) -> Result<()> {
 		state: state.clone(),
 		version: format!("v{}", clap::crate_version!()),
 		network_version: EXPECTED_SYSTEM_VERSION[0].to_string(),
-		node_client: rpc_client.clone(),
+		node_client: Some(rpc_client.clone()),
 		ws_clients: ws_clients.clone(),
 		shutdown: shutdown.clone(),
 	};
-	tokio::task::spawn(shutdown.with_cancel(server.bind()));
+	tokio::task::spawn(server.bind());
 
 	let (block_tx, block_rx) = broadcast::channel::<avail_light::types::BlockVerified>(1 << 7);
 
@@ -425,6 +498,43 @@ async fn run(shutdown: Controller<String>) -> Result<()> {
 	Ok(())
 }
 
+/// Serves the API from the database opened in read-only mode,
+/// without running the P2P network, block sync or sampling.
+/// Node is not connected, so transaction submission is disabled.
+async fn run_read_only(
+	cfg: RuntimeConfig,
+	identity_cfg: IdentityConfig,
+	shutdown: Controller<String>,
+) -> Result<()> {
+	info!("Read-only mode, only stored data is served");
+	let db = RocksDB::open_read_only(&cfg.avail_path)
+		.wrap_err("Avail Light could not open database in read-only mode")?;
+
+	let state = db
+		.stored_state(cfg.app_id)
+		.wrap_err("Avail Light could not read stored blocks")?;
+	if let Some(range) = &state.header_verified {
+		info!("Serving stored blocks {}-{}", range.first, range.last);
+	}
+	let state = Arc::new(Mutex::new(state));
+
+	let server = api::server::Server {
+		db,
+		cfg,
+		identity_cfg,
+		state,
+		version: format!("v{}", clap::crate_version!()),
+		network_version: EXPECTED_SYSTEM_VERSION[0].to_string(),
+		// node is not connected, so transactions cannot be submitted
+		node_client: None,
+		ws_clients: Default::default(),
+		shutdown,
+	};
+	tokio::task::spawn(server.bind());
+
+	Ok(())
+}
+
 fn construct_multiaddress(is_websocket: bool, port: u16) -> Multiaddr {
 	let tcp_multiaddress = Multiaddr::empty()
 		.with(Protocol::from(Ipv4Addr::UNSPECIFIED))
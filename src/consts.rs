@@ -5,6 +5,15 @@
 pub const EXPECTED_SYSTEM_VERSION: &[&str] = &["2.0"];
 pub const EXPECTED_SPEC_NAME: &str = "avail";
 
+/// Maximum nesting depth of values decoded from untrusted input, same as the one used by the runtime for extrinsics
+pub const MAX_DECODE_DEPTH: u32 = 256;
+/// Maximum size of the encoded GRANDPA justification received from the node
+pub const MAX_JUSTIFICATION_SIZE: usize = 4 * 1024 * 1024;
+/// Maximum size of the encoded finality proof received from the node
+pub const MAX_FINALITY_PROOF_SIZE: usize = 16 * 1024 * 1024;
+/// Maximum size of the encoded value read from the database
+pub const MAX_DB_VALUE_SIZE: usize = 64 * 1024 * 1024;
+
 #[derive(Clone)]
 pub struct ExpectedNodeVariant {
 	pub system_version: &'static [&'static str],
@@ -1,7 +1,8 @@
 use codec::{Decode, Encode};
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Result, WrapErr};
 use serde::{Deserialize, Serialize};
-use sp_core::ed25519;
+use sp_core::{blake2_256, ed25519, H256};
+use std::fs;
 
 pub mod rocks_db;
 
@@ -43,12 +44,16 @@ pub const STATE_CF: &str = "avail_light_state_cf";
 /// Sync finality checkpoint key name
 const FINALITY_SYNC_CHECKPOINT_KEY: &str = "finality_sync_checkpoint";
 
+/// Generated P2P secret key name
+const P2P_SECRET_KEY_KEY: &str = "p2p_secret_key";
+
 #[derive(Clone)]
 pub enum Key {
 	AppData(u32, u32),
 	BlockHeader(u32),
 	VerifiedCellCount(u32),
 	FinalitySyncCheckpoint,
+	P2PSecretKey,
 }
 
 #[derive(Serialize, Deserialize, Debug, Decode, Encode)]
@@ -57,3 +62,92 @@ pub struct FinalitySyncCheckpoint {
 	pub set_id: u64,
 	pub validator_set: Vec<ed25519::Public>,
 }
+
+/// Finality sync checkpoint exported to a file, along with the finalized block hash and checksum
+#[derive(Serialize, Deserialize)]
+struct FinalitySyncCheckpointSnapshot {
+	checkpoint: FinalitySyncCheckpoint,
+	finalized_hash: H256,
+	checksum: H256,
+}
+
+impl FinalitySyncCheckpointSnapshot {
+	fn checksum(&self) -> H256 {
+		blake2_256(&(&self.checkpoint, self.finalized_hash).encode()).into()
+	}
+}
+
+impl FinalitySyncCheckpoint {
+	/// Writes checkpoint, hash of its finalized block and checksum to the JSON file.
+	pub fn export(self, finalized_hash: H256, path: &str) -> Result<()> {
+		let mut snapshot = FinalitySyncCheckpointSnapshot {
+			checkpoint: self,
+			finalized_hash,
+			checksum: H256::zero(),
+		};
+		snapshot.checksum = snapshot.checksum();
+		let json = serde_json::to_string_pretty(&snapshot)
+			.wrap_err("Failed to serialize finality sync checkpoint")?;
+		fs::write(path, json).wrap_err("Failed to write finality sync checkpoint file")
+	}
+
+	/// Reads checkpoint and hash of its finalized block from the JSON file and verifies the checksum.
+	///
+	/// Checksum only detects corrupted files, finalized block hash needs to be checked
+	/// against the trusted node before the checkpoint is used.
+	pub fn import(path: &str) -> Result<(Self, H256)> {
+		let json =
+			fs::read_to_string(path).wrap_err("Failed to read finality sync checkpoint file")?;
+		let snapshot: FinalitySyncCheckpointSnapshot = serde_json::from_str(&json)
+			.wrap_err("Failed to deserialize finality sync checkpoint")?;
+		if snapshot.checksum() != snapshot.checksum {
+			return Err(eyre!("Finality sync checkpoint checksum mismatch"));
+		}
+		Ok((snapshot.checkpoint, snapshot.finalized_hash))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::env;
+
+	fn checkpoint_path(name: &str) -> String {
+		let path = env::temp_dir().join(format!("avail-light-{name}-{}.json", std::process::id()));
+		path.to_string_lossy().to_string()
+	}
+
+	fn checkpoint() -> FinalitySyncCheckpoint {
+		FinalitySyncCheckpoint {
+			number: 42,
+			set_id: 3,
+			validator_set: vec![ed25519::Public::from_raw([1u8; 32])],
+		}
+	}
+
+	#[test]
+	fn finality_checkpoint_export_import() {
+		let path = checkpoint_path("checkpoint-roundtrip");
+		let finalized_hash = H256::repeat_byte(7);
+		checkpoint().export(finalized_hash, &path).unwrap();
+
+		let (imported, imported_hash) = FinalitySyncCheckpoint::import(&path).unwrap();
+		assert_eq!(imported.encode(), checkpoint().encode());
+		assert_eq!(imported_hash, finalized_hash);
+		fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn finality_checkpoint_import_checksum_mismatch() {
+		let path = checkpoint_path("checkpoint-mismatch");
+		checkpoint().export(H256::repeat_byte(7), &path).unwrap();
+
+		let json = fs::read_to_string(&path).unwrap();
+		let mut snapshot: serde_json::Value = serde_json::from_str(&json).unwrap();
+		snapshot["checkpoint"]["set_id"] = 4.into();
+		fs::write(&path, snapshot.to_string()).unwrap();
+
+		assert!(FinalitySyncCheckpoint::import(&path).is_err());
+		fs::remove_file(path).unwrap();
+	}
+}
@@ -1,5 +1,6 @@
 use crate::data::{
-	Database, Key, APP_DATA_CF, BLOCK_HEADER_CF, CONFIDENCE_FACTOR_CF, FINALITY_SYNC_CHECKPOINT_KEY,
+	Database, Key, APP_DATA_CF, BLOCK_HEADER_CF, CONFIDENCE_FACTOR_CF,
+	FINALITY_SYNC_CHECKPOINT_KEY, P2P_SECRET_KEY_KEY,
 };
 use color_eyre::eyre::{eyre, Result};
 use serde::{Deserialize, Serialize};
@@ -66,6 +67,7 @@ impl From<Key> for HashMapKey {
 				HashMapKey(format!("{CONFIDENCE_FACTOR_CF}:{block_number}"))
 			},
 			Key::FinalitySyncCheckpoint => HashMapKey(FINALITY_SYNC_CHECKPOINT_KEY.to_string()),
+			Key::P2PSecretKey => HashMapKey(P2P_SECRET_KEY_KEY.to_string()),
 		}
 	}
 }
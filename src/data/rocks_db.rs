@@ -1,11 +1,23 @@
-use crate::data::{self, Key, APP_DATA_CF, BLOCK_HEADER_CF, CONFIDENCE_FACTOR_CF, STATE_CF};
+use crate::{
+	consts::MAX_DB_VALUE_SIZE,
+	data::{self, Key, APP_DATA_CF, BLOCK_HEADER_CF, CONFIDENCE_FACTOR_CF, STATE_CF},
+	types::{BlockRange, OptionBlockRange, State},
+	utils::decode_limited,
+};
 use codec::{Decode, Encode};
 use color_eyre::eyre::{eyre, Context, Result};
-use rocksdb::{ColumnFamilyDescriptor, Options};
+use rocksdb::{ColumnFamilyDescriptor, Direction, IteratorMode, Options};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{str, sync::Arc};
 
-use super::FINALITY_SYNC_CHECKPOINT_KEY;
+use super::{FINALITY_SYNC_CHECKPOINT_KEY, P2P_SECRET_KEY_KEY};
+
+#[derive(Debug)]
+pub struct ColumnFamilyStatistics {
+	pub name: &'static str,
+	pub estimated_keys: u64,
+	pub size_bytes: u64,
+}
 
 #[derive(Clone)]
 pub struct RocksDB {
@@ -13,21 +25,155 @@ pub struct RocksDB {
 }
 
 impl RocksDB {
-	pub fn open(path: &str) -> Result<RocksDB> {
-		let cf_opts = vec![
+	fn cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+		vec![
 			ColumnFamilyDescriptor::new(CONFIDENCE_FACTOR_CF, Options::default()),
 			ColumnFamilyDescriptor::new(BLOCK_HEADER_CF, Options::default()),
 			ColumnFamilyDescriptor::new(APP_DATA_CF, Options::default()),
 			ColumnFamilyDescriptor::new(STATE_CF, Options::default()),
-		];
+		]
+	}
 
+	pub fn open(path: &str) -> Result<RocksDB> {
 		let mut db_opts = Options::default();
 		db_opts.create_if_missing(true);
 		db_opts.create_missing_column_families(true);
 
-		let db = rocksdb::DB::open_cf_descriptors(&db_opts, path, cf_opts)?;
+		let db = rocksdb::DB::open_cf_descriptors(&db_opts, path, Self::cf_descriptors())?;
+		Ok(RocksDB { db: Arc::new(db) })
+	}
+
+	/// Opens existing database in read-only mode.
+	///
+	/// Database can be opened while another process holds it open for writing,
+	/// write operations on read-only instance will fail.
+	pub fn open_read_only(path: &str) -> Result<RocksDB> {
+		let db = rocksdb::DB::open_cf_descriptors_read_only(
+			&Options::default(),
+			path,
+			Self::cf_descriptors(),
+			false,
+		)?;
 		Ok(RocksDB { db: Arc::new(db) })
 	}
+
+	/// Removes all chain data, keeping the network identity stored in the state column family.
+	pub fn purge_chain_data(&self) -> Result<()> {
+		for cf in [CONFIDENCE_FACTOR_CF, BLOCK_HEADER_CF, APP_DATA_CF] {
+			self.db
+				.drop_cf(cf)
+				.wrap_err("Failed to drop Column Family from RocksDB")?;
+			self.db
+				.create_cf(cf, &Options::default())
+				.wrap_err("Failed to create Column Family in RocksDB")?;
+		}
+		data::Database::delete(self, Key::FinalitySyncCheckpoint)
+	}
+
+	/// Returns estimated number of keys and size of stored files for each column family.
+	pub fn statistics(&self) -> Result<Vec<ColumnFamilyStatistics>> {
+		[CONFIDENCE_FACTOR_CF, BLOCK_HEADER_CF, APP_DATA_CF, STATE_CF]
+			.into_iter()
+			.map(|cf| {
+				let cf_handle = self
+					.db
+					.cf_handle(cf)
+					.ok_or_else(|| eyre!("Couldn't get Column Family handle from RocksDB"))?;
+				let property = |name: &str| -> Result<u64> {
+					let value = self.db.property_int_value_cf(&cf_handle, name)?;
+					Ok(value.unwrap_or_default())
+				};
+				Ok(ColumnFamilyStatistics {
+					name: cf,
+					estimated_keys: property("rocksdb.estimate-num-keys")?,
+					size_bytes: property("rocksdb.total-sst-files-size")?,
+				})
+			})
+			.collect()
+	}
+
+	/// Returns state of the stored blocks, used to serve stored data in read-only mode.
+	///
+	/// Block ranges span from the first to the last block with stored header,
+	/// verified cell count and app data of the given application.
+	pub fn stored_state(&self, app_id: Option<u32>) -> Result<State> {
+		let header_verified = self.stored_block_range(BLOCK_HEADER_CF)?;
+		let confidence_achieved = self.stored_block_range(CONFIDENCE_FACTOR_CF)?;
+		let data_verified = match app_id {
+			Some(app_id) => self.stored_app_data_range(app_id)?,
+			None => None,
+		};
+		Ok(State {
+			latest: header_verified.last().unwrap_or_default(),
+			header_verified,
+			confidence_achieved,
+			data_verified,
+			..Default::default()
+		})
+	}
+
+	/// Returns range of blocks stored in the column family keyed by block number
+	fn stored_block_range(&self, cf: &str) -> Result<Option<BlockRange>> {
+		let cf_handle = self
+			.db
+			.cf_handle(cf)
+			.ok_or_else(|| eyre!("Couldn't get Column Family handle from RocksDB"))?;
+		let block_number = |mode: IteratorMode| -> Result<Option<u32>> {
+			let Some(entry) = self.db.iterator_cf(&cf_handle, mode).next() else {
+				return Ok(None);
+			};
+			let (key, _) = entry?;
+			let key = <[u8; 4]>::try_from(&key[..])
+				.map_err(|_| eyre!("Invalid block number key in {cf}"))?;
+			Ok(Some(u32::from_be_bytes(key)))
+		};
+		let first = block_number(IteratorMode::Start)?;
+		let last = block_number(IteratorMode::End)?;
+		Ok(first
+			.zip(last)
+			.map(|(first, last)| BlockRange { first, last }))
+	}
+
+	/// Returns range of blocks with stored app data of the given application
+	fn stored_app_data_range(&self, app_id: u32) -> Result<Option<BlockRange>> {
+		let cf_handle = self
+			.db
+			.cf_handle(APP_DATA_CF)
+			.ok_or_else(|| eyre!("Couldn't get Column Family handle from RocksDB"))?;
+		let prefix = format!("{app_id}:");
+		let mode = IteratorMode::From(prefix.as_bytes(), Direction::Forward);
+
+		let mut range: Option<BlockRange> = None;
+		for entry in self.db.iterator_cf(&cf_handle, mode) {
+			let (key, _) = entry?;
+			// app data keys are sorted as strings, so keys of other applications follow
+			let Some(block_number) = key.strip_prefix(prefix.as_bytes()) else {
+				break;
+			};
+			let block_number: u32 = str::from_utf8(block_number)?.parse()?;
+			range = Some(match range {
+				Some(BlockRange { first, last }) => BlockRange {
+					first: first.min(block_number),
+					last: last.max(block_number),
+				},
+				None => BlockRange::init(block_number),
+			});
+		}
+		Ok(range)
+	}
+
+	/// Compacts all column families, reclaiming space of deleted and overwritten entries.
+	pub fn compact(&self) -> Result<()> {
+		for cf in [CONFIDENCE_FACTOR_CF, BLOCK_HEADER_CF, APP_DATA_CF, STATE_CF] {
+			let cf_handle = self
+				.db
+				.cf_handle(cf)
+				.ok_or_else(|| eyre!("Couldn't get Column Family handle from RocksDB"))?;
+			self.db
+				.compact_range_cf(&cf_handle, None::<&[u8]>, None::<&[u8]>);
+		}
+		Ok(())
+	}
 }
 
 type RocksKey = (Option<&'static str>, Vec<u8>);
@@ -50,6 +196,7 @@ impl From<Key> for (Option<&'static str>, Vec<u8>) {
 				Some(STATE_CF),
 				FINALITY_SYNC_CHECKPOINT_KEY.as_bytes().to_vec(),
 			),
+			Key::P2PSecretKey => (Some(STATE_CF), P2P_SECRET_KEY_KEY.as_bytes().to_vec()),
 		}
 	}
 }
@@ -91,7 +238,10 @@ impl data::Database for RocksDB {
 			return self
 				.db
 				.get(key)?
-				.map(|value| <T>::decode(&mut &value[..]).wrap_err("Failed decoding the app data."))
+				.map(|value| {
+					decode_limited::<T>(&value, MAX_DB_VALUE_SIZE)
+						.wrap_err("Failed decoding the app data.")
+				})
 				.transpose()
 				.wrap_err("Get operation failed on RocksDB");
 		};
@@ -103,7 +253,10 @@ impl data::Database for RocksDB {
 
 		self.db
 			.get_cf(&cf_handle, key)?
-			.map(|value| <T>::decode(&mut &value[..]).wrap_err("Failed decoding the app data."))
+			.map(|value| {
+				decode_limited::<T>(&value, MAX_DB_VALUE_SIZE)
+					.wrap_err("Failed decoding the app data.")
+			})
 			.transpose()
 			.wrap_err("Get operation with Column Family failed on RocksDB")
 	}
@@ -127,3 +280,58 @@ impl data::Database for RocksDB {
 			.wrap_err("Delete operation with Column Family failed on RocksDB")
 	}
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use crate::data::{Database, FinalitySyncCheckpoint};
+	use std::{env, fs};
+
+	/// Returns empty database path in the temporary directory, unique for the test process
+	pub(crate) fn temp_db_path(name: &str) -> String {
+		let path = env::temp_dir().join(format!("avail-light-{name}-{}", std::process::id()));
+		_ = fs::remove_dir_all(&path);
+		path.to_string_lossy().to_string()
+	}
+
+	#[test]
+	fn purge_chain_data_keeps_network_identity() {
+		let path = temp_db_path("purge");
+		let db = RocksDB::open(&path).unwrap();
+		db.put(Key::VerifiedCellCount(1), 10u32).unwrap();
+		db.put(Key::P2PSecretKey, [1u8; 32]).unwrap();
+		let checkpoint = FinalitySyncCheckpoint {
+			number: 1,
+			set_id: 1,
+			validator_set: vec![],
+		};
+		db.put(Key::FinalitySyncCheckpoint, checkpoint).unwrap();
+
+		db.purge_chain_data().unwrap();
+
+		assert!(db.get::<u32>(Key::VerifiedCellCount(1)).unwrap().is_none());
+		let checkpoint: Option<FinalitySyncCheckpoint> =
+			db.get(Key::FinalitySyncCheckpoint).unwrap();
+		assert!(checkpoint.is_none());
+		assert_eq!(db.get(Key::P2PSecretKey).unwrap(), Some([1u8; 32]));
+
+		drop(db);
+		fs::remove_dir_all(path).unwrap();
+	}
+
+	#[test]
+	fn read_only_database_rejects_writes() {
+		let path = temp_db_path("read-only");
+		let db = RocksDB::open(&path).unwrap();
+		db.put(Key::VerifiedCellCount(1), 10u32).unwrap();
+		drop(db);
+
+		let db = RocksDB::open_read_only(&path).unwrap();
+		assert_eq!(db.get(Key::VerifiedCellCount(1)).unwrap(), Some(10u32));
+		assert!(db.put(Key::VerifiedCellCount(2), 20u32).is_err());
+		assert!(db.delete(Key::VerifiedCellCount(1)).is_err());
+
+		drop(db);
+		fs::remove_dir_all(path).unwrap();
+	}
+}
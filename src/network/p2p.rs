@@ -1,14 +1,17 @@
 use allow_block_list::BlockedPeers;
 use color_eyre::{eyre::WrapErr, Report, Result};
 use libp2p::{
-	autonat, dcutr, identify, identity,
+	autonat, connection_limits, dcutr, identify, identity,
 	kad::{self, PeerRecord, QueryId},
 	mdns, noise, ping, relay,
-	swarm::NetworkBehaviour,
+	swarm::{behaviour::toggle::Toggle, NetworkBehaviour},
 	tcp, upnp, yamux, PeerId, Swarm, SwarmBuilder,
 };
 use multihash::{self, Hasher};
-use std::collections::HashMap;
+use std::{
+	collections::{HashMap, HashSet},
+	time::Instant,
+};
 use tokio::sync::{
 	mpsc::{self},
 	oneshot,
@@ -17,12 +20,18 @@ use tracing::info;
 
 #[cfg(feature = "network-analysis")]
 pub mod analyzer;
+mod bandwidth;
 mod client;
 mod event_loop;
+mod ip_limits;
 mod kad_mem_store;
 
-use crate::types::{LibP2PConfig, SecretKey};
-pub use client::Client;
+use crate::{
+	data::{Database, Key},
+	types::{LibP2PConfig, SecretKey},
+};
+pub use bandwidth::Bandwidth;
+pub use client::{Client, PeerInfo};
 pub use event_loop::EventLoop;
 pub use kad_mem_store::MemoryStoreConfig;
 
@@ -42,6 +51,14 @@ pub struct EventLoopEntries<'a> {
 	pending_swarm_events: &'a mut HashMap<PeerId, oneshot::Sender<Result<()>>>,
 	/// <block_num, (total_cells, result_cell_counter, time_stat)>
 	active_blocks: &'a mut HashMap<u32, BlockStat>,
+	/// Identify and ping details of the connected peers
+	peers: &'a mut HashMap<PeerId, PeerInfo>,
+	/// Banned peers with their ban expiry, `None` if the ban never expires
+	banned_peers: &'a mut HashMap<PeerId, Option<Instant>>,
+	/// Peers blocked for good, i.e. non-Avail peers
+	permanently_blocked: &'a HashSet<PeerId>,
+	/// Bytes received and sent over all connections
+	bandwidth: &'a bandwidth::Counters,
 }
 
 impl<'a> EventLoopEntries<'a> {
@@ -50,12 +67,20 @@ impl<'a> EventLoopEntries<'a> {
 		pending_kad_queries: &'a mut HashMap<QueryId, QueryChannel>,
 		pending_swarm_events: &'a mut HashMap<PeerId, oneshot::Sender<Result<()>>>,
 		active_blocks: &'a mut HashMap<u32, BlockStat>,
+		peers: &'a mut HashMap<PeerId, PeerInfo>,
+		banned_peers: &'a mut HashMap<PeerId, Option<Instant>>,
+		permanently_blocked: &'a HashSet<PeerId>,
+		bandwidth: &'a bandwidth::Counters,
 	) -> Self {
 		Self {
 			swarm,
 			pending_kad_queries,
 			pending_swarm_events,
 			active_blocks,
+			peers,
+			banned_peers,
+			permanently_blocked,
+			bandwidth,
 		}
 	}
 
@@ -96,12 +121,14 @@ pub struct Behaviour {
 	kademlia: kad::Behaviour<MemoryStore>,
 	identify: identify::Behaviour,
 	ping: ping::Behaviour,
-	mdns: mdns::tokio::Behaviour,
+	mdns: Toggle<mdns::tokio::Behaviour>,
 	auto_nat: autonat::Behaviour,
 	relay_client: relay::client::Behaviour,
 	dcutr: dcutr::Behaviour,
 	upnp: upnp::tokio::Behaviour,
 	blocked_peers: allow_block_list::Behaviour<BlockedPeers>,
+	connection_limits: connection_limits::Behaviour,
+	ip_connection_limits: ip_limits::Behaviour,
 }
 
 fn generate_config(config: libp2p::swarm::Config, cfg: &LibP2PConfig) -> libp2p::swarm::Config {
@@ -118,6 +145,7 @@ async fn build_swarm(
 	id_keys: &libp2p::identity::Keypair,
 	kad_store: MemoryStore,
 	is_ws_transport: bool,
+	bandwidth: &bandwidth::Counters,
 ) -> Result<Swarm<Behaviour>> {
 	// create Identify Protocol Config
 	let identify_cfg =
@@ -148,15 +176,24 @@ async fn build_swarm(
 			dcutr: dcutr::Behaviour::new(key.public().to_peer_id()),
 			kademlia: kad::Behaviour::with_config(key.public().to_peer_id(), kad_store, cfg.into()),
 			auto_nat: autonat::Behaviour::new(key.public().to_peer_id(), autonat_cfg),
-			mdns: mdns::Behaviour::new(mdns::Config::default(), key.public().to_peer_id())?,
+			mdns: cfg
+				.mdns_enable
+				.then(|| mdns::Behaviour::new(mdns::Config::default(), key.public().to_peer_id()))
+				.transpose()?
+				.into(),
 			upnp: upnp::tokio::Behaviour::default(),
 			blocked_peers: allow_block_list::Behaviour::default(),
+			connection_limits: connection_limits::Behaviour::new(cfg.connection_limits.clone()),
+			ip_connection_limits: ip_limits::Behaviour::new(cfg.max_established_per_ip),
 		})
 	};
 
+	// relayed connections are not counted, since their bytes are already counted on the connection to the relay
 	if is_ws_transport {
 		swarm = tokio_swarm
-			.with_websocket(noise::Config::new, yamux::Config::default)
+			.with_websocket(noise::Config::new, || {
+				bandwidth.upgrade(yamux::Config::default())
+			})
 			.await?
 			.with_relay_client(noise::Config::new, yamux::Config::default)?
 			.with_behaviour(behaviour)?
@@ -167,7 +204,7 @@ async fn build_swarm(
 			.with_tcp(
 				tcp::Config::default().port_reuse(false).nodelay(false),
 				noise::Config::new,
-				yamux::Config::default,
+				|| bandwidth.upgrade(yamux::Config::default()),
 			)?
 			.with_dns()?
 			.with_relay_client(noise::Config::new, yamux::Config::default)?
@@ -191,6 +228,24 @@ async fn build_swarm(
 	Ok(swarm)
 }
 
+/// Returns secret key stored in the database, generating and storing a new one on the first start.
+/// Used when secret key is not configured, so the peer ID is kept across restarts.
+pub fn stored_secret_key(db: &impl Database) -> Result<SecretKey> {
+	let key = match db.get::<[u8; 32]>(Key::P2PSecretKey)? {
+		Some(key) => key,
+		None => {
+			let key: [u8; 32] = rand::random();
+			db.put(Key::P2PSecretKey, key)
+				.wrap_err("Failed to store generated secret key")?;
+			info!("Generated new P2P secret key");
+			key
+		},
+	};
+	Ok(SecretKey::Key {
+		key: hex::encode(key),
+	})
+}
+
 // Keypair function creates identity Keypair for a local node.
 // From such generated keypair it derives multihash identifier of the local peer.
 pub fn keypair(cfg: &LibP2PConfig) -> Result<(libp2p::identity::Keypair, String)> {
@@ -215,3 +270,49 @@ pub fn keypair(cfg: &LibP2PConfig) -> Result<(libp2p::identity::Keypair, String)
 	let peer_id = PeerId::from(keypair.public()).to_string();
 	Ok((keypair, peer_id))
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		data::{
+			mem_db::MemoryDB,
+			rocks_db::{tests::temp_db_path, RocksDB},
+		},
+		types::RuntimeConfig,
+	};
+	use std::fs;
+
+	#[test]
+	fn stored_secret_key_is_reused() {
+		let db = MemoryDB::default();
+		let SecretKey::Key { key: generated } = stored_secret_key(&db).unwrap() else {
+			panic!("Secret key should be generated");
+		};
+		let SecretKey::Key { key: stored } = stored_secret_key(&db).unwrap() else {
+			panic!("Secret key should be stored");
+		};
+		assert_eq!(generated, stored);
+	}
+
+	#[test]
+	fn peer_id_survives_clean() {
+		let path = temp_db_path("peer-id");
+		let mut cfg: LibP2PConfig = (&RuntimeConfig::default()).into();
+
+		let db = RocksDB::open(&path).unwrap();
+		cfg.secret_key = Some(stored_secret_key(&db).unwrap());
+		let (_, peer_id) = keypair(&cfg).unwrap();
+		drop(db);
+
+		// restart with `--clean`
+		let db = RocksDB::open(&path).unwrap();
+		db.purge_chain_data().unwrap();
+		cfg.secret_key = Some(stored_secret_key(&db).unwrap());
+		let (_, cleaned_peer_id) = keypair(&cfg).unwrap();
+		assert_eq!(peer_id, cleaned_peer_id);
+
+		drop(db);
+		fs::remove_dir_all(path).unwrap();
+	}
+}
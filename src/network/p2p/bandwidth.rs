@@ -0,0 +1,249 @@
+use futures::{AsyncRead, AsyncWrite};
+use libp2p::core::{
+	muxing::{StreamMuxer, StreamMuxerEvent},
+	upgrade::{InboundConnectionUpgrade, OutboundConnectionUpgrade, UpgradeInfo},
+};
+use std::{
+	future::Future,
+	io::{self, IoSlice, IoSliceMut},
+	pin::Pin,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	task::{Context, Poll},
+};
+
+/// Total number of bytes received and sent over all connections since the start
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bandwidth {
+	pub total_inbound: u64,
+	pub total_outbound: u64,
+}
+
+/// Byte counters shared by all connections
+#[derive(Clone, Debug, Default)]
+pub struct Counters {
+	inbound: Arc<AtomicU64>,
+	outbound: Arc<AtomicU64>,
+}
+
+impl Counters {
+	pub fn bandwidth(&self) -> Bandwidth {
+		Bandwidth {
+			total_inbound: self.inbound.load(Ordering::Relaxed),
+			total_outbound: self.outbound.load(Ordering::Relaxed),
+		}
+	}
+
+	/// Wraps multiplexer upgrade, so the bytes of all substreams of the upgraded connections are counted
+	pub fn upgrade<U>(&self, upgrade: U) -> Upgrade<U> {
+		Upgrade {
+			inner: upgrade,
+			counters: self.clone(),
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct Upgrade<U> {
+	inner: U,
+	counters: Counters,
+}
+
+impl<U: UpgradeInfo> UpgradeInfo for Upgrade<U> {
+	type Info = U::Info;
+	type InfoIter = U::InfoIter;
+
+	fn protocol_info(&self) -> Self::InfoIter {
+		self.inner.protocol_info()
+	}
+}
+
+impl<C, U> InboundConnectionUpgrade<C> for Upgrade<U>
+where
+	U: InboundConnectionUpgrade<C>,
+	U::Future: Unpin,
+{
+	type Output = Muxer<U::Output>;
+	type Error = U::Error;
+	type Future = UpgradeFuture<U::Future>;
+
+	fn upgrade_inbound(self, socket: C, info: Self::Info) -> Self::Future {
+		UpgradeFuture {
+			inner: self.inner.upgrade_inbound(socket, info),
+			counters: self.counters,
+		}
+	}
+}
+
+impl<C, U> OutboundConnectionUpgrade<C> for Upgrade<U>
+where
+	U: OutboundConnectionUpgrade<C>,
+	U::Future: Unpin,
+{
+	type Output = Muxer<U::Output>;
+	type Error = U::Error;
+	type Future = UpgradeFuture<U::Future>;
+
+	fn upgrade_outbound(self, socket: C, info: Self::Info) -> Self::Future {
+		UpgradeFuture {
+			inner: self.inner.upgrade_outbound(socket, info),
+			counters: self.counters,
+		}
+	}
+}
+
+pub struct UpgradeFuture<F> {
+	inner: F,
+	counters: Counters,
+}
+
+impl<F, M, E> Future for UpgradeFuture<F>
+where
+	F: Future<Output = Result<M, E>> + Unpin,
+{
+	type Output = Result<Muxer<M>, E>;
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let Poll::Ready(result) = Pin::new(&mut self.inner).poll(cx) else {
+			return Poll::Pending;
+		};
+		let counters = self.counters.clone();
+		Poll::Ready(result.map(|inner| Muxer { inner, counters }))
+	}
+}
+
+/// Multiplexer which counts the bytes of all of its substreams
+pub struct Muxer<M> {
+	inner: M,
+	counters: Counters,
+}
+
+impl<M> StreamMuxer for Muxer<M>
+where
+	M: StreamMuxer + Unpin,
+	M::Substream: Unpin,
+{
+	type Substream = Substream<M::Substream>;
+	type Error = M::Error;
+
+	fn poll_inbound(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+	) -> Poll<Result<Self::Substream, Self::Error>> {
+		let counters = self.counters.clone();
+		Pin::new(&mut self.inner)
+			.poll_inbound(cx)
+			.map_ok(|inner| Substream { inner, counters })
+	}
+
+	fn poll_outbound(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+	) -> Poll<Result<Self::Substream, Self::Error>> {
+		let counters = self.counters.clone();
+		Pin::new(&mut self.inner)
+			.poll_outbound(cx)
+			.map_ok(|inner| Substream { inner, counters })
+	}
+
+	fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		Pin::new(&mut self.inner).poll_close(cx)
+	}
+
+	fn poll(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+	) -> Poll<Result<StreamMuxerEvent, Self::Error>> {
+		Pin::new(&mut self.inner).poll(cx)
+	}
+}
+
+/// Substream which counts the bytes read from and written to it
+pub struct Substream<S> {
+	inner: S,
+	counters: Counters,
+}
+
+fn count(counter: &AtomicU64, poll: Poll<io::Result<usize>>) -> Poll<io::Result<usize>> {
+	if let Poll::Ready(Ok(bytes)) = poll {
+		counter.fetch_add(bytes as u64, Ordering::Relaxed);
+	}
+	poll
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for Substream<S> {
+	fn poll_read(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		buf: &mut [u8],
+	) -> Poll<io::Result<usize>> {
+		let poll = Pin::new(&mut self.inner).poll_read(cx, buf);
+		count(&self.counters.inbound, poll)
+	}
+
+	fn poll_read_vectored(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		bufs: &mut [IoSliceMut<'_>],
+	) -> Poll<io::Result<usize>> {
+		let poll = Pin::new(&mut self.inner).poll_read_vectored(cx, bufs);
+		count(&self.counters.inbound, poll)
+	}
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for Substream<S> {
+	fn poll_write(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		buf: &[u8],
+	) -> Poll<io::Result<usize>> {
+		let poll = Pin::new(&mut self.inner).poll_write(cx, buf);
+		count(&self.counters.outbound, poll)
+	}
+
+	fn poll_write_vectored(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		bufs: &[IoSlice<'_>],
+	) -> Poll<io::Result<usize>> {
+		let poll = Pin::new(&mut self.inner).poll_write_vectored(cx, bufs);
+		count(&self.counters.outbound, poll)
+	}
+
+	fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.inner).poll_flush(cx)
+	}
+
+	fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.inner).poll_close(cx)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::{io::Cursor, AsyncReadExt, AsyncWriteExt};
+
+	#[tokio::test]
+	async fn substream_counts_read_and_written_bytes() {
+		let counters = Counters::default();
+		let mut substream = Substream {
+			inner: Cursor::new(vec![0u8; 16]),
+			counters: counters.clone(),
+		};
+
+		let mut buf = [0u8; 10];
+		substream.read_exact(&mut buf).await.unwrap();
+		substream.write_all(&[1u8; 4]).await.unwrap();
+
+		assert_eq!(
+			counters.bandwidth(),
+			Bandwidth {
+				total_inbound: 10,
+				total_outbound: 4,
+			}
+		);
+	}
+}
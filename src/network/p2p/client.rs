@@ -1,4 +1,7 @@
-use super::{Command, CommandSender, EventLoopEntries, QueryChannel, SendableCommand};
+use super::{
+	event_loop::record_ban, Bandwidth, Command, CommandSender, EventLoopEntries, QueryChannel,
+	SendableCommand,
+};
 use color_eyre::{
 	eyre::{eyre, WrapErr},
 	Report, Result,
@@ -16,7 +19,7 @@ use libp2p::{
 };
 use std::str;
 use std::{
-	collections::HashMap,
+	collections::{HashMap, VecDeque},
 	time::{Duration, Instant},
 };
 use tokio::sync::oneshot;
@@ -80,6 +83,26 @@ impl BlockStat {
 	}
 }
 
+/// Connected peer details, received through the identify and ping protocols
+#[derive(Clone, Debug, Default)]
+pub struct PeerInfo {
+	pub agent_version: String,
+	pub protocols: Vec<String>,
+	pub listen_addrs: Vec<Multiaddr>,
+	/// Most recent ping round-trip times, oldest first
+	pub ping_rtts: VecDeque<Duration>,
+}
+
+impl PeerInfo {
+	/// Average of the recorded ping round-trip times
+	pub fn average_rtt(&self) -> Option<Duration> {
+		if self.ping_rtts.is_empty() {
+			return None;
+		}
+		Some(self.ping_rtts.iter().sum::<Duration>() / self.ping_rtts.len() as u32)
+	}
+}
+
 struct PruneExpiredRecords {
 	now: Instant,
 	response_sender: Option<oneshot::Sender<Result<usize>>>,
@@ -296,6 +319,63 @@ impl Command for ListConnectedPeers {
 	}
 }
 
+struct GetBandwidth {
+	response_sender: Option<oneshot::Sender<Result<Bandwidth>>>,
+}
+
+impl Command for GetBandwidth {
+	fn run(&mut self, entries: EventLoopEntries) -> Result<()> {
+		let bandwidth = entries.bandwidth.bandwidth();
+
+		// send result back
+		// TODO: consider what to do if this results with None
+		self.response_sender
+			.take()
+			.unwrap()
+			.send(Ok(bandwidth))
+			.expect("GetBandwidth receiver dropped");
+		Ok(())
+	}
+
+	fn abort(&mut self, error: Report) {
+		// TODO: consider what to do if this results with None
+		self.response_sender
+			.take()
+			.unwrap()
+			.send(Err(error))
+			.expect("GetBandwidth receiver dropped");
+	}
+}
+
+struct GetPeerInfo {
+	peer_id: PeerId,
+	response_sender: Option<oneshot::Sender<Result<Option<PeerInfo>>>>,
+}
+
+impl Command for GetPeerInfo {
+	fn run(&mut self, entries: EventLoopEntries) -> Result<()> {
+		let peer_info = entries.peers.get(&self.peer_id).cloned();
+
+		// send result back
+		// TODO: consider what to do if this results with None
+		self.response_sender
+			.take()
+			.unwrap()
+			.send(Ok(peer_info))
+			.expect("GetPeerInfo receiver dropped");
+		Ok(())
+	}
+
+	fn abort(&mut self, error: Report) {
+		// TODO: consider what to do if this results with None
+		self.response_sender
+			.take()
+			.unwrap()
+			.send(Err(error))
+			.expect("GetPeerInfo receiver dropped");
+	}
+}
+
 struct GetCellsInDHTPerBlock {
 	response_sender: Option<oneshot::Sender<Result<()>>>,
 }
@@ -456,6 +536,82 @@ impl Command for DialPeer {
 	}
 }
 
+struct DisconnectPeer {
+	peer_id: PeerId,
+	response_sender: Option<oneshot::Sender<Result<()>>>,
+}
+
+impl Command for DisconnectPeer {
+	fn run(&mut self, mut entries: EventLoopEntries) -> Result<()> {
+		entries
+			.swarm()
+			.disconnect_peer_id(self.peer_id)
+			.map_err(|_| eyre!("Peer {} is not connected", self.peer_id))?;
+
+		// send result back
+		// TODO: consider what to do if this results with None
+		self.response_sender
+			.take()
+			.unwrap()
+			.send(Ok(()))
+			.expect("DisconnectPeer receiver dropped");
+		Ok(())
+	}
+
+	fn abort(&mut self, error: Report) {
+		// TODO: consider what to do if this results with None
+		self.response_sender
+			.take()
+			.unwrap()
+			.send(Err(error))
+			.expect("DisconnectPeer receiver dropped");
+	}
+}
+
+struct BanPeer {
+	peer_id: PeerId,
+	duration: Duration,
+	response_sender: Option<oneshot::Sender<Result<()>>>,
+}
+
+impl Command for BanPeer {
+	fn run(&mut self, mut entries: EventLoopEntries) -> Result<()> {
+		// permanently blocked peers are already blocked and are never unbanned
+		if record_ban(
+			entries.banned_peers,
+			entries.permanently_blocked,
+			self.peer_id,
+			Instant::now(),
+			self.duration,
+		) {
+			// blocking the peer closes all of its connections and refuses the new ones
+			entries
+				.behavior_mut()
+				.blocked_peers
+				.block_peer(self.peer_id);
+			entries.behavior_mut().kademlia.remove_peer(&self.peer_id);
+		}
+
+		// send result back
+		// TODO: consider what to do if this results with None
+		self.response_sender
+			.take()
+			.unwrap()
+			.send(Ok(()))
+			.expect("BanPeer receiver dropped");
+		Ok(())
+	}
+
+	fn abort(&mut self, error: Report) {
+		// TODO: consider what to do if this results with None
+		self.response_sender
+			.take()
+			.unwrap()
+			.send(Err(error))
+			.expect("BanPeer receiver dropped");
+	}
+}
+
 struct AddAutonatServer {
 	peer_id: PeerId,
 	address: Multiaddr,
@@ -535,6 +691,29 @@ impl Client {
 		.await
 	}
 
+	/// Closes all connections to the peer
+	pub async fn disconnect_peer(&self, peer_id: PeerId) -> Result<()> {
+		self.execute_sync(|response_sender| {
+			Box::new(DisconnectPeer {
+				peer_id,
+				response_sender: Some(response_sender),
+			})
+		})
+		.await
+	}
+
+	/// Closes all connections to the peer and refuses new ones until the ban expires
+	pub async fn ban_peer(&self, peer_id: PeerId, duration: Duration) -> Result<()> {
+		self.execute_sync(|response_sender| {
+			Box::new(BanPeer {
+				peer_id,
+				duration,
+				response_sender: Some(response_sender),
+			})
+		})
+		.await
+	}
+
 	pub async fn bootstrap(&self) -> Result<()> {
 		self.execute_sync(|response_sender| {
 			Box::new(Bootstrap {
@@ -610,6 +789,27 @@ impl Client {
 		.await
 	}
 
+	/// Returns details of the connected peer, if any were received
+	pub async fn get_peer_info(&self, peer_id: PeerId) -> Result<Option<PeerInfo>> {
+		self.execute_sync(|response_sender| {
+			Box::new(GetPeerInfo {
+				peer_id,
+				response_sender: Some(response_sender),
+			})
+		})
+		.await
+	}
+
+	/// Returns total number of bytes received and sent over all connections
+	pub async fn bandwidth(&self) -> Result<Bandwidth> {
+		self.execute_sync(|response_sender| {
+			Box::new(GetBandwidth {
+				response_sender: Some(response_sender),
+			})
+		})
+		.await
+	}
+
 	async fn get_multiaddress(&self) -> Result<Vec<Multiaddr>> {
 		self.execute_sync(|response_sender| {
 			Box::new(GetMultiaddress {
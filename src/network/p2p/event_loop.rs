@@ -18,10 +18,15 @@ use libp2p::{
 	upnp, Multiaddr, PeerId, Swarm,
 };
 use rand::seq::SliceRandom;
-use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	str::FromStr,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 use tokio::{
 	sync::oneshot,
-	time::{interval_at, Instant, Interval},
+	time::{self, interval_at, Interval},
 };
 use tracing::{debug, error, info, trace, warn};
 
@@ -33,10 +38,17 @@ use crate::{
 };
 
 use super::{
-	build_swarm, client::BlockStat, Behaviour, BehaviourEvent, CommandReceiver, EventLoopEntries,
-	QueryChannel, SendableCommand,
+	bandwidth, build_swarm,
+	client::{BlockStat, PeerInfo},
+	Behaviour, BehaviourEvent, CommandReceiver, EventLoopEntries, QueryChannel, SendableCommand,
 };
 
+/// Number of most recent ping round-trip times kept per peer
+const PING_RTT_WINDOW: usize = 10;
+
+/// Interval in which expired peer bans are lifted
+const BAN_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 // RelayState keeps track of all things relay related
 struct RelayState {
 	// id of the selected Relay that needs to be connected
@@ -93,11 +105,58 @@ pub struct EventLoop {
 	bootstrap: BootstrapState,
 	/// Blocks we monitor for PUT success rate
 	active_blocks: HashMap<u32, BlockStat>,
+	/// Identify and ping details of the connected peers
+	peers: HashMap<PeerId, PeerInfo>,
+	/// Banned peers with their ban expiry, `None` if the ban never expires
+	banned_peers: HashMap<PeerId, Option<Instant>>,
+	/// Non-Avail peers, blocked for good and never unbanned
+	permanently_blocked: HashSet<PeerId>,
+	/// Bytes received and sent over all connections
+	bandwidth: bandwidth::Counters,
+	ban_expiry_timer: Interval,
 	shutdown: Controller<String>,
 
 	event_loop_config: EventLoopConfig,
 }
 
+/// Records ping round-trip time, dropping the oldest one when the window is full
+fn record_ping_rtt(ping_rtts: &mut VecDeque<Duration>, rtt: Duration) {
+	if ping_rtts.len() == PING_RTT_WINDOW {
+		ping_rtts.pop_front();
+	}
+	ping_rtts.push_back(rtt);
+}
+
+/// Records ban of the peer, returning `false` if the peer is permanently blocked and shouldn't be banned.
+/// Ban never expires if its expiry overflows.
+pub(super) fn record_ban(
+	banned_peers: &mut HashMap<PeerId, Option<Instant>>,
+	permanently_blocked: &HashSet<PeerId>,
+	peer_id: PeerId,
+	now: Instant,
+	duration: Duration,
+) -> bool {
+	if permanently_blocked.contains(&peer_id) {
+		return false;
+	}
+	banned_peers.insert(peer_id, now.checked_add(duration));
+	true
+}
+
+/// Returns banned peers with expired ban, skipping permanently blocked ones
+fn expired_bans(
+	banned_peers: &HashMap<PeerId, Option<Instant>>,
+	permanently_blocked: &HashSet<PeerId>,
+	now: Instant,
+) -> Vec<PeerId> {
+	banned_peers
+		.iter()
+		.filter(|(peer_id, _)| !permanently_blocked.contains(peer_id))
+		.filter(|(_, &expiry)| expiry.is_some_and(|expiry| expiry <= now))
+		.map(|(&peer_id, _)| peer_id)
+		.collect()
+}
+
 #[derive(PartialEq, Debug)]
 enum DHTKey {
 	Cell(u32, u32, u32),
@@ -133,7 +192,8 @@ impl EventLoop {
 		let peer_id = id_keys.public().to_peer_id();
 		let store = MemoryStore::with_config(peer_id, (&cfg).into());
 
-		let swarm = build_swarm(&cfg, id_keys, store, is_ws_transport)
+		let bandwidth = bandwidth::Counters::default();
+		let swarm = build_swarm(&cfg, id_keys, store, is_ws_transport, &bandwidth)
 			.await
 			.expect("Unable to build swarm.");
 
@@ -149,9 +209,20 @@ impl EventLoop {
 			},
 			bootstrap: BootstrapState {
 				is_startup_done: false,
-				timer: interval_at(Instant::now() + bootstrap_interval, bootstrap_interval),
+				timer: interval_at(
+					time::Instant::now() + bootstrap_interval,
+					bootstrap_interval,
+				),
 			},
 			active_blocks: Default::default(),
+			peers: Default::default(),
+			banned_peers: Default::default(),
+			permanently_blocked: Default::default(),
+			bandwidth,
+			ban_expiry_timer: interval_at(
+				time::Instant::now() + BAN_EXPIRY_CHECK_INTERVAL,
+				BAN_EXPIRY_CHECK_INTERVAL,
+			),
 			shutdown,
 			event_loop_config: EventLoopConfig {
 				identity_data: cfg.identify,
@@ -180,6 +251,7 @@ impl EventLoop {
 					},
 				},
 				_ = self.bootstrap.timer.tick() => self.handle_periodic_bootstraps(),
+				_ = self.ban_expiry_timer.tick() => self.unban_expired_peers(),
 				// if the shutdown was triggered,
 				// break the loop immediately, proceed to the cleanup phase
 				_ = self.shutdown.triggered_shutdown() => {
@@ -332,12 +404,17 @@ impl EventLoop {
 							listen_addrs,
 							agent_version,
 							protocol_version,
+							protocols,
 							..
 						},
 				} => {
 					trace!(
 						"Identity Received from: {peer_id:?} on listen address: {listen_addrs:?}"
 					);
+					let peer_info = self.peers.entry(peer_id).or_default();
+					peer_info.agent_version = agent_version.clone();
+					peer_info.protocols = protocols.iter().map(ToString::to_string).collect();
+					peer_info.listen_addrs = listen_addrs.clone();
 					let incoming_peer_agent_version = match AgentVersion::from_str(&agent_version) {
 						Ok(agent) => agent,
 						Err(e) => {
@@ -363,6 +440,8 @@ impl EventLoop {
 						debug!("Removing and blocking non-avail peer from routing table. Peer: {peer_id}. Agent: {agent_version}. Protocol: {protocol_version}");
 						self.swarm.behaviour_mut().kademlia.remove_peer(&peer_id);
 						self.swarm.behaviour_mut().blocked_peers.block_peer(peer_id);
+						self.banned_peers.remove(&peer_id);
+						self.permanently_blocked.insert(peer_id);
 					}
 				},
 				identify::Event::Sent { peer_id } => {
@@ -389,13 +468,13 @@ impl EventLoop {
 					for (peer_id, multiaddr) in addrs_list {
 						trace!("MDNS got expired peer with ID: {peer_id:#?} and Address: {multiaddr:#?}");
 
-						if self
+						let is_discovered = self
 							.swarm
-							.behaviour_mut()
+							.behaviour()
 							.mdns
-							.discovered_nodes()
-							.any(|&p| p == peer_id)
-						{
+							.as_ref()
+							.is_some_and(|mdns| mdns.discovered_nodes().any(|&p| p == peer_id));
+						if is_discovered {
 							self.swarm
 								.behaviour_mut()
 								.kademlia
@@ -437,8 +516,10 @@ impl EventLoop {
 					trace!("Hole punching failed with: {remote_peer_id:#?}. Error: {err:#?}")
 				},
 			},
-			SwarmEvent::Behaviour(BehaviourEvent::Ping(ping::Event { result, .. })) => {
+			SwarmEvent::Behaviour(BehaviourEvent::Ping(ping::Event { peer, result, .. })) => {
 				if let Ok(rtt) = result {
+					record_ping_rtt(&mut self.peers.entry(peer).or_default().ping_rtts, rtt);
+
 					let _ = metrics
 						.record(MetricValue::PingLatency(rtt.as_millis() as f64))
 						.await;
@@ -472,6 +553,10 @@ impl EventLoop {
 					} => {
 						trace!("Connection closed. PeerID: {peer_id:?}. Address: {:?}. Num established: {num_established:?}. Cause: {cause:?}", endpoint.get_remote_address());
 
+						if num_established == 0 {
+							self.peers.remove(&peer_id);
+						}
+
 						if let Some(ConnectionError::IO(_)) = cause {
 							// remove peer with failed connection
 							self.swarm.behaviour_mut().kademlia.remove_peer(&peer_id);
@@ -540,6 +625,10 @@ impl EventLoop {
 			&mut self.pending_kad_queries,
 			&mut self.pending_swarm_events,
 			&mut self.active_blocks,
+			&mut self.peers,
+			&mut self.banned_peers,
+			&self.permanently_blocked,
+			&self.bandwidth,
 		)) {
 			command.abort(eyre!(err));
 		}
@@ -553,6 +642,23 @@ impl EventLoop {
 		}
 	}
 
+	fn unban_expired_peers(&mut self) {
+		let expired_peers = expired_bans(
+			&self.banned_peers,
+			&self.permanently_blocked,
+			Instant::now(),
+		);
+
+		for peer_id in expired_peers {
+			debug!("Ban expired for peer: {peer_id}");
+			self.banned_peers.remove(&peer_id);
+			self.swarm
+				.behaviour_mut()
+				.blocked_peers
+				.unblock_peer(peer_id);
+		}
+	}
+
 	fn establish_relay_circuit(&mut self, peer_id: PeerId) {
 		// before we try and create a circuit with the relay
 		// we have to exchange observed addresses
@@ -660,9 +766,16 @@ impl EventLoop {
 
 #[cfg(test)]
 mod tests {
-	use crate::network::p2p::event_loop::DHTKey;
+	use crate::network::p2p::{
+		client::PeerInfo,
+		event_loop::{expired_bans, record_ban, record_ping_rtt, DHTKey, PING_RTT_WINDOW},
+	};
 	use color_eyre::Result;
-	use libp2p::kad::RecordKey;
+	use libp2p::{kad::RecordKey, PeerId};
+	use std::{
+		collections::{HashMap, HashSet},
+		time::{Duration, Instant},
+	};
 
 	#[test]
 	fn dht_key_parse_record_key() {
@@ -678,4 +791,108 @@ mod tests {
 		let result: Result<DHTKey> = RecordKey::new(&"123").try_into();
 		_ = result.unwrap_err();
 	}
+
+	#[test]
+	fn ping_rtt_window_and_average() {
+		let mut peer_info = PeerInfo::default();
+		assert_eq!(peer_info.average_rtt(), None);
+
+		for millis in 1..=PING_RTT_WINDOW as u64 + 2 {
+			record_ping_rtt(&mut peer_info.ping_rtts, Duration::from_millis(millis * 10));
+		}
+
+		// two oldest round-trip times are evicted
+		assert_eq!(peer_info.ping_rtts.len(), PING_RTT_WINDOW);
+		assert_eq!(
+			peer_info.ping_rtts.front(),
+			Some(&Duration::from_millis(30))
+		);
+		assert_eq!(
+			peer_info.ping_rtts.back(),
+			Some(&Duration::from_millis(120))
+		);
+		assert_eq!(peer_info.average_rtt(), Some(Duration::from_millis(75)));
+	}
+
+	#[test]
+	fn expired_ban_is_lifted() {
+		let mut banned_peers = HashMap::new();
+		let permanently_blocked = HashSet::new();
+		let (expired, active) = (PeerId::random(), PeerId::random());
+		let now = Instant::now();
+
+		assert!(record_ban(
+			&mut banned_peers,
+			&permanently_blocked,
+			expired,
+			now,
+			Duration::from_secs(10)
+		));
+		assert!(record_ban(
+			&mut banned_peers,
+			&permanently_blocked,
+			active,
+			now,
+			Duration::from_secs(60)
+		));
+
+		assert!(expired_bans(&banned_peers, &permanently_blocked, now).is_empty());
+		let later = now + Duration::from_secs(10);
+		assert_eq!(
+			expired_bans(&banned_peers, &permanently_blocked, later),
+			vec![expired]
+		);
+	}
+
+	#[test]
+	fn overflowing_ban_never_expires() {
+		let mut banned_peers = HashMap::new();
+		let permanently_blocked = HashSet::new();
+		let peer_id = PeerId::random();
+		let now = Instant::now();
+
+		assert!(record_ban(
+			&mut banned_peers,
+			&permanently_blocked,
+			peer_id,
+			now,
+			Duration::MAX
+		));
+		assert_eq!(banned_peers.get(&peer_id), Some(&None));
+
+		let later = now + Duration::from_secs(100 * 365 * 24 * 60 * 60);
+		assert!(expired_bans(&banned_peers, &permanently_blocked, later).is_empty());
+	}
+
+	#[test]
+	fn permanently_blocked_peer_is_never_unbanned() {
+		let mut banned_peers = HashMap::new();
+		let peer_id = PeerId::random();
+		let now = Instant::now();
+
+		// peer banned before it turned out to be a non-Avail peer
+		let mut permanently_blocked = HashSet::new();
+		assert!(record_ban(
+			&mut banned_peers,
+			&permanently_blocked,
+			peer_id,
+			now,
+			Duration::from_secs(10)
+		));
+		permanently_blocked.insert(peer_id);
+
+		let later = now + Duration::from_secs(10);
+		assert!(expired_bans(&banned_peers, &permanently_blocked, later).is_empty());
+
+		// banning it again isn't recorded
+		banned_peers.clear();
+		assert!(!record_ban(
+			&mut banned_peers,
+			&permanently_blocked,
+			peer_id,
+			now,
+			Duration::from_secs(10)
+		));
+		assert!(banned_peers.is_empty());
+	}
 }
@@ -0,0 +1,188 @@
+use libp2p::{
+	core::Endpoint,
+	multiaddr::Protocol,
+	swarm::{
+		dummy, ConnectionClosed, ConnectionDenied, ConnectionId, FromSwarm, NetworkBehaviour,
+		THandler, THandlerInEvent, THandlerOutEvent, ToSwarm,
+	},
+	Multiaddr, PeerId,
+};
+use std::{
+	collections::HashMap,
+	fmt,
+	net::IpAddr,
+	task::{Context, Poll},
+};
+use void::Void;
+
+/// Limits the number of established incoming connections per remote IP address.
+///
+/// Connections over the limit are denied before the handshake, as soon as they are pending.
+pub struct Behaviour {
+	max_established_per_ip: Option<u32>,
+	/// Established incoming connections with their remote IP address
+	established: HashMap<ConnectionId, IpAddr>,
+}
+
+#[derive(Debug)]
+pub struct Exceeded {
+	ip: IpAddr,
+	limit: u32,
+}
+
+impl fmt::Display for Exceeded {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"Connection limit of {} per IP exceeded for {}",
+			self.limit, self.ip
+		)
+	}
+}
+
+impl std::error::Error for Exceeded {}
+
+fn remote_ip(address: &Multiaddr) -> Option<IpAddr> {
+	address.iter().find_map(|protocol| match protocol {
+		Protocol::Ip4(ip) => Some(IpAddr::V4(ip)),
+		Protocol::Ip6(ip) => Some(IpAddr::V6(ip)),
+		_ => None,
+	})
+}
+
+impl Behaviour {
+	pub fn new(max_established_per_ip: Option<u32>) -> Self {
+		Self {
+			max_established_per_ip,
+			established: Default::default(),
+		}
+	}
+
+	fn check_limit(&self, remote_addr: &Multiaddr) -> Result<(), ConnectionDenied> {
+		let (Some(limit), Some(ip)) = (self.max_established_per_ip, remote_ip(remote_addr)) else {
+			return Ok(());
+		};
+		let count = self
+			.established
+			.values()
+			.filter(|&&addr| addr == ip)
+			.count();
+		if count >= limit as usize {
+			return Err(ConnectionDenied::new(Exceeded { ip, limit }));
+		}
+		Ok(())
+	}
+}
+
+impl NetworkBehaviour for Behaviour {
+	type ConnectionHandler = dummy::ConnectionHandler;
+	type ToSwarm = Void;
+
+	fn handle_pending_inbound_connection(
+		&mut self,
+		_: ConnectionId,
+		_: &Multiaddr,
+		remote_addr: &Multiaddr,
+	) -> Result<(), ConnectionDenied> {
+		self.check_limit(remote_addr)
+	}
+
+	fn handle_established_inbound_connection(
+		&mut self,
+		connection_id: ConnectionId,
+		_: PeerId,
+		_: &Multiaddr,
+		remote_addr: &Multiaddr,
+	) -> Result<THandler<Self>, ConnectionDenied> {
+		// other connections from the same IP could have been established while this one was pending
+		self.check_limit(remote_addr)?;
+		if let Some(ip) = remote_ip(remote_addr) {
+			self.established.insert(connection_id, ip);
+		}
+		Ok(dummy::ConnectionHandler)
+	}
+
+	fn handle_established_outbound_connection(
+		&mut self,
+		_: ConnectionId,
+		_: PeerId,
+		_: &Multiaddr,
+		_: Endpoint,
+	) -> Result<THandler<Self>, ConnectionDenied> {
+		Ok(dummy::ConnectionHandler)
+	}
+
+	fn on_swarm_event(&mut self, event: FromSwarm) {
+		if let FromSwarm::ConnectionClosed(ConnectionClosed { connection_id, .. }) = event {
+			self.established.remove(&connection_id);
+		}
+	}
+
+	fn on_connection_handler_event(
+		&mut self,
+		_: PeerId,
+		_: ConnectionId,
+		event: THandlerOutEvent<Self>,
+	) {
+		void::unreachable(event)
+	}
+
+	fn poll(&mut self, _: &mut Context<'_>) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
+		Poll::Pending
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn address(ip: &str) -> Multiaddr {
+		format!("/ip4/{ip}/tcp/37000").parse().unwrap()
+	}
+
+	#[test]
+	fn limits_established_incoming_connections_per_ip() {
+		let mut behaviour = Behaviour::new(Some(1));
+		let local = address("127.0.0.1");
+
+		assert!(behaviour
+			.handle_established_inbound_connection(
+				ConnectionId::new_unchecked(1),
+				PeerId::random(),
+				&local,
+				&address("10.0.0.1"),
+			)
+			.is_ok());
+		assert!(behaviour
+			.handle_pending_inbound_connection(
+				ConnectionId::new_unchecked(2),
+				&local,
+				&address("10.0.0.1")
+			)
+			.is_err());
+		assert!(behaviour
+			.handle_pending_inbound_connection(
+				ConnectionId::new_unchecked(3),
+				&local,
+				&address("10.0.0.2")
+			)
+			.is_ok());
+	}
+
+	#[test]
+	fn no_limit_when_not_configured() {
+		let mut behaviour = Behaviour::new(None);
+		let local = address("127.0.0.1");
+
+		for id in 0..10 {
+			assert!(behaviour
+				.handle_established_inbound_connection(
+					ConnectionId::new_unchecked(id),
+					PeerId::random(),
+					&local,
+					&address("10.0.0.1"),
+				)
+				.is_ok());
+		}
+	}
+}
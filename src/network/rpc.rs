@@ -18,9 +18,11 @@ use tokio::{
 use tracing::{debug, info};
 
 use crate::{
+	consts::{MAX_FINALITY_PROOF_SIZE, MAX_JUSTIFICATION_SIZE},
 	data::Database,
 	network::rpc,
 	types::{GrandpaJustification, RetryConfig, State},
+	utils::decode_limited,
 };
 
 mod client;
@@ -51,7 +53,7 @@ pub struct WrappedJustification(pub GrandpaJustification);
 impl Decode for WrappedJustification {
 	fn decode<I: codec::Input>(input: &mut I) -> std::result::Result<Self, codec::Error> {
 		let j: Vec<u8> = Decode::decode(input)?;
-		let jj: GrandpaJustification = Decode::decode(&mut &j[..])?;
+		let jj: GrandpaJustification = decode_limited(&j, MAX_JUSTIFICATION_SIZE)?;
 		Ok(WrappedJustification(jj))
 	}
 }
@@ -76,7 +78,8 @@ impl<'de> Deserialize<'de> for WrappedProof {
 	{
 		let data = from_hex(&String::deserialize(deserializer)?)
 			.map_err(|e| de::Error::custom(format!("{:?}", e)))?;
-		Decode::decode(&mut &data[..]).map_err(|e| de::Error::custom(format!("{:?}", e)))
+		decode_limited(&data, MAX_FINALITY_PROOF_SIZE)
+			.map_err(|e| de::Error::custom(format!("{:?}", e)))
 	}
 }
 
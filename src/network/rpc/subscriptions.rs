@@ -6,6 +6,7 @@ use sp_core::{
 	ed25519::{self, Public},
 };
 use std::{
+	cmp::Reverse,
 	sync::{Arc, Mutex},
 	time::Instant,
 };
@@ -22,6 +23,9 @@ use crate::{
 	utils::filter_auth_set_changes,
 };
 
+/// Maximum number of unverified headers kept while waiting for justifications
+const MAX_UNVERIFIED_HEADERS: usize = 256;
+
 #[derive(Clone, Debug)]
 pub enum Event {
 	HeaderUpdate {
@@ -38,6 +42,37 @@ struct BlockData {
 	last_finalized_block_header: Option<Header>,
 }
 
+impl BlockData {
+	/// Removes headers and justifications which cannot be finalized anymore.
+	///
+	/// Headers at or below the finalized height are removed. Headers above it are kept even
+	/// if their parent is unknown, since the finalized headers subscription skips blocks.
+	/// Only the newest `MAX_UNVERIFIED_HEADERS` headers are kept.
+	fn prune_stale(&mut self, finalized_header: &Header) {
+		let finalized_number = finalized_header.number;
+
+		self.justifications
+			.retain(|justification| justification.commit.target_number > finalized_number);
+
+		let headers_count = self.unverified_headers.len();
+		self.unverified_headers
+			.retain(|(header, _, _)| header.number > finalized_number);
+		if self.unverified_headers.len() > MAX_UNVERIFIED_HEADERS {
+			self.unverified_headers
+				.sort_by_key(|(header, _, _)| Reverse(header.number));
+			self.unverified_headers.truncate(MAX_UNVERIFIED_HEADERS);
+		}
+
+		let pruned_count = headers_count - self.unverified_headers.len();
+		if pruned_count > 0 {
+			debug!(
+				finalized_number,
+				"Pruned {pruned_count} of {headers_count} unverified headers"
+			);
+		}
+	}
+}
+
 pub struct SubscriptionLoop<T: Database> {
 	rpc_client: Client,
 	event_sender: Sender<Event>,
@@ -237,6 +272,8 @@ impl<T: Database> SubscriptionLoop<T> {
 				info!("Sending finalized block {}", header.number);
 				// reset Last Finalized Block Header
 				self.block_data.last_finalized_block_header = Some(header.clone());
+				// drop forks and justifications below the finalized block
+				self.block_data.prune_stale(&header);
 
 				// finally, send the Verified Block Header
 				self.state
@@ -258,3 +295,140 @@ impl<T: Database> SubscriptionLoop<T> {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::Commit;
+	use avail_subxt::api::runtime_types::avail_core::{
+		data_lookup::compact::CompactDataLookup,
+		header::extension::{v3, HeaderExtension},
+		kate_commitment::v3::KateCommitment,
+	};
+	use sp_core::H256;
+	use subxt::config::substrate::Digest;
+
+	fn header(number: u32, parent_hash: H256) -> Header {
+		Header {
+			parent_hash,
+			number,
+			state_root: H256::default(),
+			extrinsics_root: H256::default(),
+			extension: HeaderExtension::V3(v3::HeaderExtension {
+				commitment: KateCommitment::default(),
+				app_lookup: CompactDataLookup {
+					size: 0,
+					index: vec![],
+				},
+			}),
+			digest: Digest { logs: vec![] },
+		}
+	}
+
+	fn hash(header: &Header) -> H256 {
+		Encode::using_encoded(header, blake2_256).into()
+	}
+
+	fn justification(target_number: u32) -> GrandpaJustification {
+		GrandpaJustification {
+			round: 1,
+			commit: Commit {
+				target_hash: H256::default(),
+				target_number,
+				precommits: vec![],
+			},
+			votes_ancestries: vec![],
+		}
+	}
+
+	fn block_data(headers: Vec<Header>, justifications: Vec<GrandpaJustification>) -> BlockData {
+		let valset = ValidatorSet {
+			set_id: 0,
+			validator_set: vec![],
+		};
+		BlockData {
+			justifications,
+			unverified_headers: headers
+				.into_iter()
+				.map(|header| (header, Instant::now(), valset.clone()))
+				.collect(),
+			current_valset: valset,
+			next_valset: None,
+			last_finalized_block_header: None,
+		}
+	}
+
+	fn unverified_hashes(block_data: &BlockData) -> Vec<H256> {
+		block_data
+			.unverified_headers
+			.iter()
+			.map(|(header, _, _)| hash(header))
+			.collect()
+	}
+
+	#[test]
+	fn prune_stale_removes_headers_at_or_below_finalized_height() {
+		let finalized = header(10, H256::repeat_byte(1));
+		let below = header(9, H256::repeat_byte(2));
+		let fork = header(10, H256::repeat_byte(3));
+		let child = header(11, hash(&finalized));
+		let mut block_data = block_data(vec![below, fork, child.clone()], vec![]);
+
+		block_data.prune_stale(&finalized);
+		assert_eq!(unverified_hashes(&block_data), vec![hash(&child)]);
+	}
+
+	#[test]
+	fn prune_stale_keeps_headers_with_skipped_parents() {
+		let finalized = header(10, H256::repeat_byte(1));
+		// parent at height 12 was skipped by the subscription
+		let skipped_parent = header(13, H256::repeat_byte(2));
+		let child = header(11, hash(&finalized));
+		let mut block_data = block_data(vec![skipped_parent.clone(), child.clone()], vec![]);
+
+		block_data.prune_stale(&finalized);
+		assert_eq!(
+			unverified_hashes(&block_data),
+			vec![hash(&skipped_parent), hash(&child)]
+		);
+	}
+
+	#[test]
+	fn prune_stale_keeps_newest_headers() {
+		let finalized = header(10, H256::repeat_byte(1));
+		let count = MAX_UNVERIFIED_HEADERS as u32 + 5;
+		let headers = (11..11 + count)
+			.map(|number| header(number, H256::repeat_byte(2)))
+			.collect();
+		let mut block_data = block_data(headers, vec![]);
+
+		block_data.prune_stale(&finalized);
+		let numbers = block_data
+			.unverified_headers
+			.iter()
+			.map(|(header, _, _)| header.number)
+			.collect::<Vec<_>>();
+		assert_eq!(numbers.len(), MAX_UNVERIFIED_HEADERS);
+		assert_eq!(numbers.iter().min(), Some(&16));
+		assert_eq!(numbers.iter().max(), Some(&(10 + count)));
+	}
+
+	#[test]
+	fn prune_stale_removes_finalized_justifications() {
+		let finalized = header(10, H256::repeat_byte(1));
+		let below = header(9, H256::repeat_byte(2));
+		let mut block_data = block_data(
+			vec![below, finalized.clone()],
+			vec![justification(9), justification(10), justification(11)],
+		);
+
+		block_data.prune_stale(&finalized);
+		assert!(block_data.unverified_headers.is_empty());
+		let target_numbers = block_data
+			.justifications
+			.iter()
+			.map(|justification| justification.commit.target_number)
+			.collect::<Vec<_>>();
+		assert_eq!(target_numbers, vec![11]);
+	}
+}
@@ -9,7 +9,7 @@ use futures::future::join_all;
 use sp_core::{
 	blake2_256,
 	ed25519::{self},
-	twox_128, H256,
+	H256,
 };
 use std::{
 	iter::zip,
@@ -24,7 +24,7 @@ use crate::{
 	network::rpc::{self, WrappedProof},
 	shutdown::Controller,
 	types::State,
-	utils::filter_auth_set_changes,
+	utils::{filter_auth_set_changes, storage_map_key, storage_prefix, StorageHasher},
 };
 
 #[async_trait]
@@ -159,9 +159,7 @@ async fn get_valset_at_genesis(
 	client: &impl Client,
 	genesis_hash: H256,
 ) -> Result<Vec<ed25519::Public>> {
-	let mut k1 = twox_128("Session".as_bytes()).to_vec();
-	let mut k2 = twox_128("KeyOwner".as_bytes()).to_vec();
-	k1.append(&mut k2);
+	let k1 = storage_prefix("Session", "KeyOwner");
 
 	let validator_set_pre = client
 		.get_validator_set_at(genesis_hash)
@@ -171,22 +169,26 @@ async fn get_valset_at_genesis(
 
 	// Get all grandpa session keys from genesis (GRANDPA ed25519 keys)
 	let grandpa_keys = client
-		// get all storage keys that correspond to Session_KeyOwner query, then filter by "gran"
-		// perhaps there is a better way, but I don't know it
-		.get_paged_storage_keys(k1.clone(), 1000, None, Some(genesis_hash))
+		// get all storage keys that correspond to Session_KeyOwner query, then filter grandpa ones
+		.get_paged_storage_keys(k1, 1000, None, Some(genesis_hash))
 		.await
 		.wrap_err("Couldn't get storage keys associated with key owners!")?
 		.into_iter()
-		// throw away the beginning, we don't need it
-		.map(|e| e.0[k1.len()..].to_vec())
-		.filter(|e| {
-			// exclude the actual key (at the end of the storage key) from search, we may find "gran" by accident
-			e[..(e.len() - GRANDPA_KEY_LEN)]
-				.windows(GRANDPA_KEY_ID.len())
-				.any(|e| e == GRANDPA_KEY_ID)
+		.filter_map(|StorageKey(key)| {
+			// public key is at the end of the storage key
+			let public_key: [u8; GRANDPA_KEY_LEN] = key
+				.get(key.len().checked_sub(GRANDPA_KEY_LEN)?..)?
+				.try_into()
+				.ok()?;
+			// key owner map is keyed by `(KeyTypeId, Vec<u8>)`, hashed with `Twox64Concat`
+			let grandpa_key = storage_map_key(
+				"Session",
+				"KeyOwner",
+				StorageHasher::Twox64Concat,
+				&(GRANDPA_KEY_ID, public_key.to_vec()),
+			);
+			(key == grandpa_key).then(|| ed25519::Public::from_raw(public_key))
 		})
-		.map(|e| e.as_slice()[e.len() - GRANDPA_KEY_LEN..].to_vec())
-		.map(|e| ed25519::Public::from_raw(e.try_into().expect("Vector isn't 32 bytes long")))
 		.collect::<Vec<ed25519::Public>>();
 
 	let grandpa_account_results = join_all(
@@ -1,8 +1,9 @@
 //! Shared light client structs and enums.
 
+use crate::consts::MAX_JUSTIFICATION_SIZE;
 use crate::network::p2p::MemoryStoreConfig;
 use crate::network::rpc::{Event, Node as RpcNode};
-use crate::utils::{extract_app_lookup, extract_kate};
+use crate::utils::{decode_limited, extract_app_lookup, extract_kate};
 use avail_core::DataLookup;
 use avail_subxt::{primitives::Header as DaHeader, utils::H256};
 use bip39::{Language, Mnemonic, MnemonicType};
@@ -17,7 +18,7 @@ use kate_recovery::{
 	matrix::{Dimensions, Partition},
 };
 use libp2p::kad::Mode as KadMode;
-use libp2p::{Multiaddr, PeerId};
+use libp2p::{connection_limits::ConnectionLimits, Multiaddr, PeerId};
 use serde::{de::Error, Deserialize, Serialize};
 use sp_core::crypto::Ss58Codec;
 use sp_core::{blake2_256, bytes, ed25519};
@@ -55,9 +56,24 @@ pub struct CliOpts {
 	/// Testnet or devnet selection
 	#[arg(short, long, value_name = "network")]
 	pub network: Option<Network>,
-	/// Run a clean light client, deleting existing avail_path folder
+	/// Run a clean light client, purging chain data from the database and keeping the network identity
 	#[arg(long)]
 	pub clean: bool,
+	/// Compact the database on startup
+	#[arg(long)]
+	pub compact: bool,
+	/// Print database storage statistics on startup
+	#[arg(long)]
+	pub db_stats: bool,
+	/// Open the database in read-only mode and only serve stored data over the API
+	#[arg(long)]
+	pub read_only: bool,
+	/// Import finality sync checkpoint from the file on startup, after checking it against the node
+	#[arg(long, value_name = "FILE")]
+	pub import_finality_checkpoint: Option<String>,
+	/// Export stored finality sync checkpoint to the file on startup
+	#[arg(long, value_name = "FILE")]
+	pub export_finality_checkpoint: Option<String>,
 	/// Enable finality sync
 	#[arg(short, long, value_name = "finality_sync_enable")]
 	pub finality_sync_enable: bool,
@@ -327,10 +343,16 @@ pub struct RuntimeConfig {
 	pub http_server_host: String,
 	/// Light client HTTP server port (default: 7000).
 	pub http_server_port: u16,
+	/// Interval in which ping messages are sent to connected API websocket clients, must be greater than zero and shorter than `ws_idle_timeout` (default: 30 sec).
+	pub ws_ping_interval: u64,
+	/// API websocket connection is closed if no message is received from the client in this period, must be greater than zero (default: 120 sec).
+	pub ws_idle_timeout: u64,
+	/// API websocket connection is closed after this period regardless of activity, must be greater than zero if set (default: None).
+	pub ws_max_lifetime: Option<u64>,
 	/// Secret key for libp2p keypair. Can be either set to `seed` or to `key`.
 	/// If set to seed, keypair will be generated from that seed.
 	/// If set to key, a valid ed25519 private key must be provided, else the client will fail
-	/// If `secret_key` is not set, random seed will be used.
+	/// If `secret_key` is not set, random key is generated on the first start and stored in the database, `--clean` keeps it.
 	pub secret_key: Option<SecretKey>,
 	/// P2P service port (default: 37000).
 	pub port: u16,
@@ -349,6 +371,8 @@ pub struct RuntimeConfig {
 	pub bootstraps: Vec<MultiaddrConfig>,
 	/// Defines a period of time in which periodic bootstraps will be repeated. (default: 300 sec)
 	pub bootstrap_period: u64,
+	/// Enables mDNS discovery of peers on the local network (default: true).
+	pub mdns_enable: bool,
 	pub operation_mode: KademliaMode,
 	/// Vector of Relay nodes, which are used for hole punching
 	pub relays: Vec<MultiaddrConfig>,
@@ -413,6 +437,16 @@ pub struct RuntimeConfig {
 	pub task_command_buffer_size: usize,
 	pub per_connection_event_buffer_size: usize,
 	pub dial_concurrency_factor: u8,
+	/// Maximum number of pending incoming connections, which are limited before the handshake (default: None).
+	pub max_pending_incoming: Option<u32>,
+	/// Maximum number of established incoming connections (default: None).
+	pub max_established_incoming: Option<u32>,
+	/// Maximum number of established outgoing connections (default: None).
+	pub max_established_outgoing: Option<u32>,
+	/// Maximum number of established connections per peer (default: None).
+	pub max_established_per_peer: Option<u32>,
+	/// Maximum number of established incoming connections per remote IP address, which are limited before the handshake (default: None).
+	pub max_established_per_ip: Option<u32>,
 	/// Sets the timeout for a single Kademlia query. (default: 60s).
 	pub store_pruning_interval: u32,
 	/// Sets the allowed level of parallelism for iterative Kademlia queries. (default: 3).
@@ -520,11 +554,14 @@ pub struct LibP2PConfig {
 	pub kademlia: KademliaConfig,
 	pub relays: Vec<(PeerId, Multiaddr)>,
 	pub bootstrap_interval: Duration,
+	pub mdns_enable: bool,
 	pub connection_idle_timeout: Duration,
 	pub max_negotiating_inbound_streams: usize,
 	pub task_command_buffer_size: NonZeroUsize,
 	pub per_connection_event_buffer_size: usize,
 	pub dial_concurrency_factor: NonZeroU8,
+	pub connection_limits: ConnectionLimits,
+	pub max_established_per_ip: Option<u32>,
 }
 
 impl From<&LibP2PConfig> for libp2p::kad::Config {
@@ -573,6 +610,7 @@ impl From<&RuntimeConfig> for LibP2PConfig {
 			kademlia: val.into(),
 			relays: val.relays.iter().map(Into::into).collect(),
 			bootstrap_interval: Duration::from_secs(val.bootstrap_period),
+			mdns_enable: val.mdns_enable,
 			connection_idle_timeout: Duration::from_secs(val.connection_idle_timeout),
 			max_negotiating_inbound_streams: val.max_negotiating_inbound_streams,
 			task_command_buffer_size: std::num::NonZeroUsize::new(val.task_command_buffer_size)
@@ -580,6 +618,12 @@ impl From<&RuntimeConfig> for LibP2PConfig {
 			per_connection_event_buffer_size: val.per_connection_event_buffer_size,
 			dial_concurrency_factor: std::num::NonZeroU8::new(val.dial_concurrency_factor)
 				.expect("Invalid dial concurrency factor"),
+			connection_limits: ConnectionLimits::default()
+				.with_max_pending_incoming(val.max_pending_incoming)
+				.with_max_established_incoming(val.max_established_incoming)
+				.with_max_established_outgoing(val.max_established_outgoing)
+				.with_max_established_per_peer(val.max_established_per_peer),
+			max_established_per_ip: val.max_established_per_ip,
 		}
 	}
 }
@@ -756,6 +800,9 @@ impl Default for RuntimeConfig {
 		RuntimeConfig {
 			http_server_host: "127.0.0.1".to_owned(),
 			http_server_port: 7000,
+			ws_ping_interval: 30,
+			ws_idle_timeout: 120,
+			ws_max_lifetime: None,
 			port: 37000,
 			ws_transport_enable: false,
 			secret_key: None,
@@ -766,6 +813,7 @@ impl Default for RuntimeConfig {
 			autonat_boot_delay: 5,
 			bootstraps: vec![],
 			bootstrap_period: 3600,
+			mdns_enable: true,
 			relays: Vec::new(),
 			full_node_ws: vec!["ws://127.0.0.1:9944".to_owned()],
 			genesis_hash: "DEV".to_owned(),
@@ -793,6 +841,11 @@ impl Default for RuntimeConfig {
 			task_command_buffer_size: 32,
 			per_connection_event_buffer_size: 7,
 			dial_concurrency_factor: 8,
+			max_pending_incoming: None,
+			max_established_incoming: None,
+			max_established_outgoing: None,
+			max_established_per_peer: None,
+			max_established_per_ip: None,
 			store_pruning_interval: 180,
 			query_timeout: 10,
 			query_parallelism: 3,
@@ -949,6 +1002,25 @@ impl RuntimeConfig {
 			})
 		}
 
+		self.validate()
+	}
+
+	fn validate(&self) -> Result<()> {
+		if self.ws_ping_interval == 0 || self.ws_idle_timeout == 0 {
+			return Err(eyre!(
+				"Websocket ping interval and idle timeout must be greater than zero"
+			));
+		}
+		if self.ws_ping_interval >= self.ws_idle_timeout {
+			return Err(eyre!(
+				"Websocket ping interval must be shorter than websocket idle timeout"
+			));
+		}
+		if self.ws_max_lifetime == Some(0) {
+			return Err(eyre!(
+				"Websocket maximum connection lifetime must be greater than zero"
+			));
+		}
 		Ok(())
 	}
 }
@@ -1094,7 +1166,7 @@ impl<'de> Deserialize<'de> for GrandpaJustification {
 		D: serde::Deserializer<'de>,
 	{
 		let encoded = bytes::deserialize(deserializer)?;
-		Self::decode(&mut &encoded[..])
+		decode_limited(&encoded, MAX_JUSTIFICATION_SIZE)
 			.map_err(|codec_err| D::Error::custom(format!("Invalid decoding: {:?}", codec_err)))
 	}
 }
@@ -1,3 +1,4 @@
+use crate::consts::MAX_DECODE_DEPTH;
 use avail_core::{
 	data_lookup::compact::{CompactDataLookup, DataLookupItem},
 	data_lookup::Error as DataLookupError,
@@ -14,16 +15,33 @@ use avail_subxt::{
 	},
 	utils::H256,
 };
-use codec::Decode;
+use codec::{Decode, DecodeLimit, Encode};
 use color_eyre::{eyre::WrapErr, Result};
 use kate_recovery::{
 	data::Cell,
 	matrix::{Dimensions, Position},
 };
+use sp_core::{blake2_128, twox_128, twox_64};
+
+/// Decodes value from untrusted input, limiting input size and nesting depth
+pub fn decode_limited<T: Decode>(
+	data: &[u8],
+	max_size: usize,
+) -> std::result::Result<T, codec::Error> {
+	if data.len() > max_size {
+		return Err("Encoded value exceeds the size limit".into());
+	}
+	T::decode_with_depth_limit(MAX_DECODE_DEPTH, &mut &data[..])
+}
+
+/// Decodes extrinsic from untrusted input, limiting its nesting depth
+pub fn decode_extrinsic(data: &[u8]) -> Result<AppUncheckedExtrinsic> {
+	AppUncheckedExtrinsic::decode_with_depth_limit(MAX_DECODE_DEPTH, &mut &data[..])
+		.wrap_err("Couldn't decode AvailExtrinsic")
+}
 
 pub fn decode_app_data(data: &[u8]) -> Result<Option<Vec<u8>>> {
-	let extrisic: AppUncheckedExtrinsic =
-		<_ as Decode>::decode(&mut &data[..]).wrap_err("Couldn't decode AvailExtrinsic")?;
+	let extrisic = decode_extrinsic(data)?;
 
 	match extrisic.function {
 		RuntimeCall::DataAvailability(Call::submit_data { data, .. }) => Ok(Some(data.0)),
@@ -89,6 +107,34 @@ pub fn filter_auth_set_changes(header: &DaHeader) -> Vec<Vec<(AuthorityId, u64)>
 	new_auths
 }
 
+/// Computes storage key prefix of the pallet storage item (`twox_128(pallet) ++ twox_128(item)`)
+pub fn storage_prefix(pallet: &str, item: &str) -> Vec<u8> {
+	[twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()
+}
+
+/// Hashers used for storage map keys
+pub enum StorageHasher {
+	Identity,
+	Twox64Concat,
+	Blake2_128Concat,
+}
+
+/// Computes storage key of the map entry hashed with the given hasher
+pub fn storage_map_key(
+	pallet: &str,
+	item: &str,
+	hasher: StorageHasher,
+	key: &impl Encode,
+) -> Vec<u8> {
+	let key = key.encode();
+	let hashed_key = match hasher {
+		StorageHasher::Identity => key,
+		StorageHasher::Twox64Concat => [twox_64(&key).to_vec(), key].concat(),
+		StorageHasher::Blake2_128Concat => [blake2_128(&key).to_vec(), key].concat(),
+	};
+	[storage_prefix(pallet, item), hashed_key].concat()
+}
+
 // TODO: Remove unused functions if not needed after next iteration
 
 #[allow(dead_code)]
@@ -113,7 +159,12 @@ fn diff_positions(positions: &[Position], cells: &[Cell]) -> Vec<Position> {
 
 #[cfg(test)]
 mod tests {
-	use super::{can_reconstruct, diff_positions};
+	use super::{
+		can_reconstruct, decode_limited, diff_positions, storage_map_key, storage_prefix,
+		StorageHasher,
+	};
+	use codec::{Decode, Encode};
+	use hex_literal::hex;
 	use kate_recovery::{
 		data::Cell,
 		matrix::{Dimensions, Position},
@@ -177,4 +228,51 @@ mod tests {
 		assert_eq!(diff_positions(&positions, &cells)[0], position(0, 0));
 		assert_eq!(diff_positions(&positions, &cells)[1], position(1, 1));
 	}
+
+	#[test]
+	fn test_storage_prefix() {
+		assert_eq!(
+			storage_prefix("System", "Account"),
+			hex!("26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9")
+		);
+	}
+
+	#[test]
+	fn test_storage_map_key() {
+		assert_eq!(
+			storage_map_key("System", "BlockHash", StorageHasher::Twox64Concat, &0u32),
+			hex!("26aa394eea5630e07c48ae0c9558cef7a44704b568d21667356a5a050c118746b4def25cfda6ef3a00000000")
+		);
+
+		let prefix = storage_prefix("System", "BlockHash");
+		let key = storage_map_key("System", "BlockHash", StorageHasher::Identity, &0u32);
+		assert_eq!(key, [prefix.clone(), vec![0, 0, 0, 0]].concat());
+
+		let key = storage_map_key(
+			"System",
+			"BlockHash",
+			StorageHasher::Blake2_128Concat,
+			&0u32,
+		);
+		assert!(key.starts_with(&prefix));
+		assert!(key.ends_with(&[0, 0, 0, 0]));
+		assert_eq!(key.len(), 32 + 16 + 4);
+	}
+
+	#[derive(Decode, Encode)]
+	struct Nested(Vec<Nested>);
+
+	fn nested(depth: usize) -> Nested {
+		(0..depth).fold(Nested(vec![]), |inner, _| Nested(vec![inner]))
+	}
+
+	#[test]
+	fn test_decode_limited() {
+		let shallow = nested(10).encode();
+		assert!(decode_limited::<Nested>(&shallow, 1024).is_ok());
+		assert!(decode_limited::<Nested>(&shallow, shallow.len() - 1).is_err());
+
+		let deep = nested(1000).encode();
+		assert!(decode_limited::<Nested>(&deep, deep.len()).is_err());
+	}
 }